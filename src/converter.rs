@@ -1,9 +1,521 @@
 use std::fs;
+use std::io::{BufReader, Read};
+use std::path::Path;
 
+mod block;
+mod config;
+mod encoding;
+mod errors;
+#[cfg(feature = "http")]
+mod fetch;
 mod parser;
 
-pub fn convert(path: &std::path::Path) {
-    let file_content = fs::read_to_string(path).expect(&format!("Failed to open file: {:?}", path));
+pub use block::{AttributeCardinality, Graph, Node, NodeOrToken, Stats, ValueType};
+pub use config::{Config, ConfigBuilder, LogLevel, MinimizeStrategy};
+pub use encoding::{DecodedChunks, decode};
+pub use errors::ConvertError;
+#[cfg(feature = "http")]
+pub use fetch::{fetch, is_url};
+pub use parser::{
+    ChunkedTokens, GraphError, LexError, SpannedToken, Token, Tokens, XmlDeclaration, lex_graph,
+    lex_graph_into, lex_spanned_tokens, lex_tokens,
+};
 
-    println!("{}", file_content);
-}
\ No newline at end of file
+/// The output of a conversion together with the warnings that came up while building the graph.
+/// Conversions that take several documents merge them into one graph first, see [`build_merged_graph`].
+pub type Conversion<T> = Result<(T, Vec<String>), ConvertError>;
+
+/// Files larger than this are read and lexed in chunks while the graph is built, instead of at once.
+const STREAMING_THRESHOLD: u64 = 64 * 1024 * 1024;
+
+/// The number of bytes that are read at a time from a document that is read in chunks.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// A document to convert, either a string or a file that is read while converting it.
+/// Large files are read in chunks, so their text is never in memory as a whole. The graph that is built from them
+/// still holds every element and token until it's minimized, so the memory it takes grows with the document.
+#[derive(Debug, Clone, Copy)]
+pub enum Document<'a> {
+    Str(&'a str),
+    File(&'a Path),
+}
+
+impl<'a> From<&'a str> for Document<'a> {
+    fn from(xml: &'a str) -> Document<'a> {
+        Document::Str(xml)
+    }
+}
+
+impl<'a> From<&'a Path> for Document<'a> {
+    fn from(path: &'a Path) -> Document<'a> {
+        Document::File(path)
+    }
+}
+
+/// The tokens of a document together with their spans, or the error that stopped lexing it.
+type DocumentTokens<'a> = Box<dyn Iterator<Item = Result<SpannedToken, ConvertError>> + 'a>;
+
+/// Lexes the document. Strings and small files are lexed at once, larger files are read and lexed in chunks while
+/// the tokens are consumed.
+fn document_tokens(document: Document) -> Result<DocumentTokens, ConvertError> {
+    let xml = match document {
+        Document::Str(xml) => {
+            return Ok(Box::new(
+                parser::lex_spanned_tokens(xml)?.into_iter().map(Ok),
+            ));
+        }
+        Document::File(path) => {
+            // The errors of reading a file say which file it is, there can be several.
+            let in_file = |err: std::io::Error| {
+                std::io::Error::new(err.kind(), format!("{}: {}", path.display(), err))
+            };
+            let mut file = fs::File::open(path).map_err(in_file)?;
+            if file.metadata().map_err(in_file)?.len() > STREAMING_THRESHOLD {
+                return Ok(Box::new(reader_tokens(BufReader::new(file))));
+            }
+            let mut bytes = Vec::new();
+            file.read_to_end(&mut bytes).map_err(in_file)?;
+            decode(&bytes).map_err(in_file)?
+        }
+    };
+
+    Ok(Box::new(
+        parser::lex_spanned_tokens(&xml)?.into_iter().map(Ok),
+    ))
+}
+
+/// Lexes the document while it's read in chunks.
+fn reader_tokens(reader: impl Read) -> impl Iterator<Item = Result<SpannedToken, ConvertError>> {
+    ChunkedTokens::new(
+        DecodedChunks::new(reader, CHUNK_SIZE).map(|chunk| chunk.map_err(ConvertError::from)),
+    )
+}
+
+/// Builds the graph from the tokens as they are lexed. A lex error stops building the graph and is returned
+/// instead of the errors that the unfinished graph would give. Returns the number of tokens.
+fn lex_graph_from(
+    graph: &mut Graph,
+    tokens: impl Iterator<Item = Result<SpannedToken, ConvertError>>,
+    config: &Config,
+) -> Result<usize, ConvertError> {
+    let mut count = 0;
+    let mut error = None;
+    let tokens = tokens.map_while(|token| match token {
+        Ok(token) => {
+            count += 1;
+            Some(token)
+        }
+        Err(err) => {
+            error = Some(err);
+            None
+        }
+    });
+    let built = parser::lex_graph_into(graph, tokens, config);
+    if let Some(err) = error {
+        return Err(err);
+    }
+    built?;
+
+    Ok(count)
+}
+
+/// Builds the minimized graph of an XML document.
+pub fn build_graph(xml: &str, config: &Config) -> Result<Graph, ConvertError> {
+    build_merged_graph(&[xml], config)
+}
+
+/// Builds the minimized graph of an XML document that is read in chunks while it's lexed, so the document is never
+/// read into a string. Before it's minimized the graph holds every element and token of the document, which takes
+/// several times the size of the document in memory. The encoding is detected like [`decode`] does.
+pub fn build_graph_from_reader(reader: impl Read, config: &Config) -> Result<Graph, ConvertError> {
+    let mut graph = Graph::new();
+    let count = lex_graph_from(&mut graph, reader_tokens(reader), config)?;
+    config.log(LogLevel::Info, || {
+        format!(
+            "Lexed {} tokens, building a graph of {} elements",
+            count,
+            graph.iter().count()
+        )
+    });
+    reduce(&mut graph, config);
+
+    Ok(graph)
+}
+
+/// Builds one minimized graph of several XML documents, as if their root elements were siblings.
+/// Similar documents are merged by minimize, which gives an overview of the whole corpus.
+pub fn build_merged_graph<'a>(
+    documents: &[impl Into<Document<'a>> + Copy],
+    config: &Config,
+) -> Result<Graph, ConvertError> {
+    let mut graph = lex_documents(documents, config)?;
+    reduce(&mut graph, config);
+
+    Ok(graph)
+}
+
+/// Keeps only the elements at the paths of the config, if there are any, and minimizes what's left.
+fn reduce(graph: &mut Graph, config: &Config) {
+    if !config.only.is_empty() {
+        graph.keep_only(&config.only);
+    }
+    if !config.no_minimize {
+        graph.minimize(config);
+        config.log(LogLevel::Info, || {
+            format!("Minimized the graph to {} elements", graph.iter().count())
+        });
+    }
+}
+
+/// Builds the graph of the documents, as they are without minimizing.
+fn lex_documents<'a>(
+    documents: &[impl Into<Document<'a>> + Copy],
+    config: &Config,
+) -> Result<Graph, ConvertError> {
+    let mut graph = Graph::new();
+    for (index, document) in documents.iter().enumerate() {
+        let count = lex_graph_from(&mut graph, document_tokens((*document).into())?, config)?;
+        config.log(LogLevel::Info, || {
+            format!("Lexed {} tokens from document {}", count, index + 1)
+        });
+    }
+    config.log(LogLevel::Info, || {
+        format!("Built a graph of {} elements", graph.iter().count())
+    });
+
+    Ok(graph)
+}
+
+/// Returns the summary metrics of the documents together with the warnings that came up while building the graph.
+/// The metrics describe the documents as they are, before minimizing.
+pub fn convert_stats<'a>(
+    documents: &[impl Into<Document<'a>> + Copy],
+    config: &Config,
+) -> Conversion<Stats> {
+    let graph = lex_documents(documents, config)?;
+
+    Ok((graph.stats(), graph.warnings().to_vec()))
+}
+
+/// Returns how many times every element name occurs in the documents, as `count  name` lines with the counts
+/// aligned, the most frequent first. The counts are of the documents as they are, before minimizing.
+pub fn convert_name_frequencies<'a>(
+    documents: &[impl Into<Document<'a>> + Copy],
+    config: &Config,
+) -> Conversion<String> {
+    let graph = lex_documents(documents, config)?;
+    let frequencies = graph.name_frequencies();
+    let width = frequencies
+        .first()
+        .map_or(0, |(_, count)| count.to_string().len());
+    let lines: Vec<String> = frequencies
+        .iter()
+        .map(|(name, count)| format!("{:>width$}  {}", count, name))
+        .collect();
+
+    Ok((lines.join("\n"), graph.warnings().to_vec()))
+}
+
+/// Returns the tokens of the documents, one per line in their debug format, without building a graph.
+/// There are no warnings, those only come up while building the graph.
+pub fn convert_tokens<'a>(documents: &[impl Into<Document<'a>> + Copy]) -> Conversion<String> {
+    let mut lines = Vec::<String>::new();
+    for document in documents {
+        for token in document_tokens((*document).into())? {
+            lines.push(format!("{:?}", token?.0));
+        }
+    }
+
+    Ok((lines.join("\n"), Vec::new()))
+}
+
+/// Renders the graph of the document together with the warnings that came up while building it.
+fn convert_with<'a>(
+    documents: &[impl Into<Document<'a>> + Copy],
+    config: &Config,
+    render: impl Fn(&Graph) -> String,
+) -> Conversion<String> {
+    let graph = build_merged_graph(documents, config)?;
+
+    Ok((render(&graph), graph.warnings().to_vec()))
+}
+
+/// Reads the file and returns its overview together with the warnings that came up while building it.
+/// The encoding of the file is detected, see [`decode`].
+/// Large files are read in chunks, see [`Document`].
+pub fn convert(path: &Path, config: &Config) -> Conversion<String> {
+    convert_merged(&[path], config)
+}
+
+/// Downloads the document and returns its overview together with the warnings that came up while building it.
+#[cfg(feature = "http")]
+pub fn convert_url(
+    url: &str,
+    timeout: Option<std::time::Duration>,
+    config: &Config,
+) -> Conversion<String> {
+    convert_str(&fetch(url, timeout)?, config)
+}
+
+/// Returns the overview of the document together with the warnings that came up while building it.
+pub fn convert_str(xml: &str, config: &Config) -> Conversion<String> {
+    convert_merged(&[xml], config)
+}
+
+/// Returns one overview of all documents together with the warnings that came up while building it.
+pub fn convert_merged<'a>(
+    documents: &[impl Into<Document<'a>> + Copy],
+    config: &Config,
+) -> Conversion<String> {
+    convert_with(documents, config, |graph| graph.print(config))
+}
+
+/// Returns the overview of only the elements with the given name or path and their subtrees, together with the
+/// warnings that came up while building it. Fails when no element matches, see [`Graph::print_element`].
+pub fn convert_element<'a>(
+    documents: &[impl Into<Document<'a>> + Copy],
+    name: &str,
+    config: &Config,
+) -> Conversion<String> {
+    let graph = build_merged_graph(documents, config)?;
+    let overview = graph
+        .print_element(name, config)
+        .ok_or_else(|| ConvertError::ElementNotFound(String::from(name)))?;
+
+    Ok((overview, graph.warnings().to_vec()))
+}
+
+/// Returns the minimized tree as JSON together with the warnings that came up while building it.
+#[cfg(feature = "serde")]
+pub fn convert_json<'a>(
+    documents: &[impl Into<Document<'a>> + Copy],
+    config: &Config,
+) -> Conversion<String> {
+    convert_with(documents, config, Graph::to_json)
+}
+
+/// Returns a JSON Schema for the documents together with the warnings that came up while building it.
+#[cfg(feature = "serde")]
+pub fn convert_json_schema<'a>(
+    documents: &[impl Into<Document<'a>> + Copy],
+    config: &Config,
+) -> Conversion<String> {
+    convert_with(documents, config, Graph::to_json_schema)
+}
+
+/// Returns the minimized tree as YAML together with the warnings that came up while building it.
+pub fn convert_yaml<'a>(
+    documents: &[impl Into<Document<'a>> + Copy],
+    config: &Config,
+) -> Conversion<String> {
+    convert_with(documents, config, Graph::to_yaml)
+}
+
+/// Returns the minimized tree as a Graphviz digraph together with the warnings that came up while building it.
+pub fn convert_dot<'a>(
+    documents: &[impl Into<Document<'a>> + Copy],
+    config: &Config,
+) -> Conversion<String> {
+    convert_with(documents, config, Graph::to_dot)
+}
+
+/// Returns every distinct element path, one per line, together with the warnings that came up while building it.
+pub fn convert_paths<'a>(
+    documents: &[impl Into<Document<'a>> + Copy],
+    config: &Config,
+) -> Conversion<String> {
+    convert_with(documents, config, |graph| graph.to_paths().join("\n"))
+}
+
+/// Returns every distinct path to a leaf element, an element without element children, one per line, together
+/// with the warnings that came up while building it.
+pub fn convert_leaf_paths<'a>(
+    documents: &[impl Into<Document<'a>> + Copy],
+    config: &Config,
+) -> Conversion<String> {
+    convert_with(documents, config, |graph| graph.to_leaf_paths().join("\n"))
+}
+
+/// Returns a Markdown table of the elements together with the warnings that came up while building it.
+pub fn convert_markdown<'a>(
+    documents: &[impl Into<Document<'a>> + Copy],
+    config: &Config,
+) -> Conversion<String> {
+    convert_with(documents, config, Graph::to_markdown)
+}
+
+/// Returns an XSD schema skeleton for the documents together with the warnings that came up while building it.
+pub fn convert_xsd<'a>(
+    documents: &[impl Into<Document<'a>> + Copy],
+    config: &Config,
+) -> Conversion<String> {
+    convert_with(documents, config, Graph::to_xsd)
+}
+
+/// Returns a DTD for the documents together with the warnings that came up while building it.
+pub fn convert_dtd<'a>(
+    documents: &[impl Into<Document<'a>> + Copy],
+    config: &Config,
+) -> Conversion<String> {
+    convert_with(documents, config, Graph::to_dtd)
+}
+
+/// Returns serde struct definitions for the elements together with the warnings that came up while building them.
+pub fn convert_rust<'a>(
+    documents: &[impl Into<Document<'a>> + Copy],
+    config: &Config,
+) -> Conversion<String> {
+    convert_with(documents, config, Graph::to_rust)
+}
+
+/// Returns TypeScript interfaces for the elements together with the warnings that came up while building them.
+pub fn convert_typescript<'a>(
+    documents: &[impl Into<Document<'a>> + Copy],
+    config: &Config,
+) -> Conversion<String> {
+    convert_with(documents, config, Graph::to_typescript)
+}
+
+/// Returns the minimized graph as a MessagePack snapshot together with the warnings that came up while building it.
+#[cfg(feature = "serde")]
+pub fn convert_msgpack<'a>(
+    documents: &[impl Into<Document<'a>> + Copy],
+    config: &Config,
+) -> Conversion<Vec<u8>> {
+    let graph = build_merged_graph(documents, config)?;
+
+    Ok((graph.to_msgpack()?, graph.warnings().to_vec()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert_missing_file() {
+        let result = convert(
+            std::path::Path::new("does/not/exist.xml"),
+            &Config::default(),
+        );
+
+        assert!(matches!(result, Err(ConvertError::Io(_))));
+    }
+
+    #[test]
+    fn test_convert_str() {
+        let (overview, warnings) = convert_str("<a><b/><b/>", &Config::default()).unwrap();
+
+        assert_eq!(overview, "<a><b/></a>");
+        assert_eq!(warnings, vec![String::from("<a> is never closed")]);
+    }
+
+    #[test]
+    fn test_convert_str_empty() {
+        for xml in ["", "   ", "\n\n"] {
+            let (overview, warnings) = convert_str(xml, &Config::default()).unwrap();
+
+            assert_eq!(overview, "", "{:?}", xml);
+            assert!(warnings.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_build_graph_from_reader() {
+        let xml = format!(
+            "<?xml version=\"1.0\"?>\n<catalog>{}</catalog>",
+            "<book id=\"1\"><!-- A book --><title>Tëxt</title></book>\n".repeat(5000)
+        );
+        let config = Config {
+            counts: true,
+            ..Config::default()
+        };
+        let streamed = build_graph_from_reader(xml.as_bytes(), &config).unwrap();
+
+        assert_eq!(
+            streamed.print(&config),
+            build_graph(&xml, &config).unwrap().print(&config)
+        );
+        let book = streamed.find_by_name("book")[0];
+        assert_eq!(
+            &xml[book.span.clone()],
+            xml[book.span.start..].lines().next().unwrap()
+        );
+
+        assert!(matches!(
+            build_graph_from_reader(&b"<a>\n<!-- never closed"[..], &config),
+            Err(ConvertError::Lex(LexError::UnterminatedComment {
+                line: 2,
+                column: 1
+            }))
+        ));
+    }
+
+    #[test]
+    fn test_build_graph_from_reader_large_tokens() {
+        let large = "x".repeat(CHUNK_SIZE * 3);
+        let xml = format!(
+            "<a><!--{0}--><b><![CDATA[{0}]]></b><c>{0}</c><d e=\"{0}\"/></a>",
+            large
+        );
+        let config = Config::default();
+        let streamed = build_graph_from_reader(xml.as_bytes(), &config).unwrap();
+
+        assert_eq!(
+            streamed.print(&config),
+            build_graph(&xml, &config).unwrap().print(&config)
+        );
+        assert_eq!(streamed.find_by_name("d")[0].values, vec![large]);
+    }
+
+    #[test]
+    fn test_convert_tokens() {
+        let (tokens, warnings) = convert_tokens(&["<a x=\"1\">", "Text</a>"]).unwrap();
+
+        assert_eq!(
+            tokens,
+            "TagOpenStart(\"a\")\nWhitespace\nKey(\"x\")\nString(\"1\")\nTagClosing\n\
+            Text(\"Text\")\nTagCloseStart(\"a\")\nTagClosing"
+        );
+        assert!(warnings.is_empty());
+        assert!(convert_tokens(&["<!-- open"]).is_err());
+    }
+
+    #[test]
+    fn test_convert_name_frequencies() {
+        let xml = format!("<list>{}<note/></list>", "<item/>".repeat(12));
+        let (frequencies, warnings) =
+            convert_name_frequencies(&[xml.as_str()], &Config::default()).unwrap();
+
+        assert_eq!(frequencies, "12  item\n 1  list\n 1  note");
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_convert_merged() {
+        let documents = [
+            "<?xml version=\"1.0\"?><catalog><book id=\"1\"/></catalog>",
+            "<catalog><book id=\"2\"><title/></book><magazine/></catalog>",
+        ];
+        let (overview, _) = convert_merged(&documents, &Config::default()).unwrap();
+
+        assert_eq!(
+            overview,
+            "<?xml version=\"1.0\"?>\n<catalog><book id=\"2\"><title/></book><magazine/></catalog>"
+        );
+    }
+
+    #[test]
+    fn test_build_graph_no_minimize() {
+        let xml = format!("<list>{}</list>", "<item/>".repeat(10));
+        let print = |config: Config| build_graph(&xml, &config).unwrap().print(&config);
+
+        assert_eq!(print(Config::default()), "<list><item/></list>");
+        assert_eq!(
+            print(Config {
+                no_minimize: true,
+                ..Config::default()
+            }),
+            xml
+        );
+    }
+}
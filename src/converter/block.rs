@@ -0,0 +1,2246 @@
+mod content_model;
+mod dot;
+mod dtd;
+mod fields;
+#[cfg(feature = "serde")]
+mod json;
+#[cfg(feature = "serde")]
+mod json_schema;
+mod markdown;
+mod paths;
+mod rust;
+mod stats;
+mod tree;
+mod typescript;
+mod xsd;
+mod yaml;
+
+pub use stats::{AttributeCardinality, Stats};
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::ops::Range;
+
+use anstyle::{AnsiColor, Style};
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+use crate::converter::config::{Config, LogLevel, MinimizeStrategy};
+use crate::converter::parser::{
+    Token, XmlDeclaration, decode_entities, escape_attribute, escape_text, split_qualified_name,
+};
+
+/// Version of the MessagePack snapshot layout, bump it whenever the serialized graph changes shape.
+#[cfg(feature = "serde")]
+pub const MSGPACK_FORMAT_VERSION: u32 = 15;
+
+/// Shortens a value to at most `max_len` characters followed by an ellipsis, `0` means unlimited.
+fn truncate(value: &str, max_len: usize) -> String {
+    match value.char_indices().nth(max_len) {
+        Some((index, _)) if max_len > 0 => format!("{}…", &value[..index]),
+        _ => String::from(value),
+    }
+}
+
+/// Replaces every run of whitespace by a single newline if it contains one, or by a single space.
+fn collapse_whitespace(text: &str) -> String {
+    let mut res = String::with_capacity(text.len());
+    for c in text.chars() {
+        if !c.is_whitespace() {
+            res.push(c);
+        } else if c == '\n' && res.ends_with(' ') {
+            res.pop();
+            res.push('\n');
+        } else if !res.ends_with([' ', '\n']) {
+            res.push(if c == '\n' { '\n' } else { ' ' });
+        }
+    }
+
+    res
+}
+
+/// Regex-like suffix for an item that occurs between `min` and `max` times.
+fn cardinality(min: usize, max: usize) -> &'static str {
+    match (min, max) {
+        (0, 0..=1) => "?",
+        (0, _) => "*",
+        (_, 0..=1) => "",
+        _ => "+",
+    }
+}
+
+/// Splits a name into lowercase words at punctuation and at the start of camel case humps.
+fn words(name: &str) -> Vec<String> {
+    let mut words = Vec::<String>::new();
+    let mut previous: Option<char> = None;
+    for c in name.chars() {
+        if !c.is_alphanumeric() {
+            previous = None;
+            continue;
+        }
+        match (words.last_mut(), previous) {
+            (Some(word), Some(p)) if !(p.is_lowercase() && c.is_uppercase()) => {
+                word.extend(c.to_lowercase())
+            }
+            _ => words.push(c.to_lowercase().collect()),
+        }
+        previous = Some(c);
+    }
+
+    words
+}
+
+/// Joins the words of a name in pascal case, like `BookItem` for `book-item`.
+fn pascal_case(name: &str) -> String {
+    words(name)
+        .iter()
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// The colors of the overview on a terminal, see [`Config::color`].
+const ELEMENT_STYLE: Style = Style::new()
+    .fg_color(Some(anstyle::Color::Ansi(AnsiColor::Blue)))
+    .bold();
+const ATTRIBUTE_STYLE: Style = Style::new().fg_color(Some(anstyle::Color::Ansi(AnsiColor::Cyan)));
+const TEXT_STYLE: Style = Style::new().fg_color(Some(anstyle::Color::Ansi(AnsiColor::Green)));
+
+/// Wraps the text in the escape codes of the style when the config asks for color.
+/// Whitespace around the text stays outside of the codes, so trimming and joining whitespace keep working.
+fn paint(text: &str, style: Style, config: &Config) -> String {
+    let content = text.trim();
+    if !config.color || content.is_empty() {
+        return String::from(text);
+    }
+
+    let start = text.len() - text.trim_start().len();
+    format!(
+        "{}{}{}{}{}",
+        &text[..start],
+        style.render(),
+        content,
+        style.render_reset(),
+        &text[start + content.len()..]
+    )
+}
+
+/// Starts a new line indented for an item `depth` levels deep, unless nothing has been printed yet.
+fn new_line(config: &Config, depth: usize, res: &mut String) {
+    if !res.is_empty() {
+        res.push('\n');
+        res.push_str(&" ".repeat(config.indent * depth));
+    }
+}
+
+/// Prints a token that appeared between the tags of an element.
+fn print_token(token: &Token, config: &Config, res: &mut String) {
+    match token {
+        Token::Text(text) => {
+            let mut text = if config.raw_text {
+                text.clone()
+            } else {
+                decode_entities(text)
+            };
+            if config.normalize_whitespace {
+                text = collapse_whitespace(&text);
+                if res.ends_with([' ', '\n']) && text.starts_with([' ', '\n']) {
+                    text.remove(0);
+                }
+            }
+            let text = truncate(&text, config.max_value_len);
+            // Raw text is printed as it appears, decoded text is escaped again to keep the overview valid XML.
+            if config.raw_text {
+                res.push_str(&paint(&text, TEXT_STYLE, config));
+            } else {
+                res.push_str(&paint(&escape_text(&text), TEXT_STYLE, config));
+            }
+        }
+        Token::CData(text) => res.push_str(&format!(
+            "<![CDATA[{}]]>",
+            truncate(text, config.max_value_len)
+        )),
+        Token::Comment(comment) if config.verbose => res.push_str(&format!("<!--{}-->", comment)),
+        // A run of whitespace becomes a single newline if it contains one, or a single space.
+        Token::Whitespace if config.normalize_whitespace && res.ends_with([' ', '\n']) => {}
+        Token::Whitespace => res.push(' '),
+        Token::Newline if config.normalize_whitespace => {
+            res.truncate(res.trim_end_matches(' ').len());
+            if !res.ends_with('\n') {
+                res.push('\n');
+            }
+        }
+        Token::Newline if !res.ends_with('\n') => res.push('\n'),
+        _ => (),
+    }
+}
+
+/// The number of whitespace and newline tokens at the end of the children, which lay out the closing tag.
+fn trailing_layout(children: &[NodeOrToken]) -> usize {
+    children
+        .iter()
+        .rev()
+        .take_while(|child| matches!(child, NodeOrToken::T(Token::Whitespace | Token::Newline)))
+        .count()
+}
+
+/// The type of a value, inferred from what the value looks like.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum ValueType {
+    Int,
+    Float,
+    Bool,
+    Date,
+    String,
+}
+
+impl ValueType {
+    /// Picks the most specific type that fits the value, falling back to `String`.
+    pub fn classify(value: &str) -> ValueType {
+        let value = value.trim();
+        if value.parse::<i64>().is_ok() {
+            ValueType::Int
+        } else if value.contains(|c: char| c.is_ascii_digit()) && value.parse::<f64>().is_ok() {
+            ValueType::Float
+        } else if value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case("false") {
+            ValueType::Bool
+        } else if is_date(value) {
+            ValueType::Date
+        } else {
+            ValueType::String
+        }
+    }
+
+    /// The type that fits values of both types. Integers widen to floats, any other disagreement to `String`.
+    pub fn widen(self, other: ValueType) -> ValueType {
+        match (self, other) {
+            (a, b) if a == b => a,
+            (ValueType::Int, ValueType::Float) | (ValueType::Float, ValueType::Int) => {
+                ValueType::Float
+            }
+            _ => ValueType::String,
+        }
+    }
+
+    pub fn placeholder(&self) -> &'static str {
+        match self {
+            ValueType::Int => "{int}",
+            ValueType::Float => "{float}",
+            ValueType::Bool => "{bool}",
+            ValueType::Date => "{date}",
+            ValueType::String => "{string}",
+        }
+    }
+}
+
+/// Matches `YYYY-MM-DD`, optionally followed by a time like `T12:00:00`.
+fn is_date(value: &str) -> bool {
+    let bytes = value.as_bytes();
+    if bytes.len() < 10 {
+        return false;
+    }
+
+    let date_matches = bytes[..10].iter().enumerate().all(|(i, b)| match i {
+        4 | 7 => *b == b'-',
+        _ => b.is_ascii_digit(),
+    });
+    date_matches && (bytes.len() == 10 || bytes[10] == b'T' || bytes[10] == b' ')
+}
+
+/// A child of a node, either another element or a token that appeared between the tags.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum NodeOrToken {
+    N(usize),
+    T(Token),
+}
+
+/// An element of the XML tree. Children refer to other nodes by their index in the graph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Node {
+    pub id: usize,
+    /// The qualified name of the element, as it appears in the document.
+    pub name: String,
+    /// The namespace prefix of the name, like `soap` for `soap:Envelope`.
+    pub prefix: Option<String>,
+    /// The name without its namespace prefix.
+    pub local_name: String,
+    pub keys: Vec<String>,
+    /// A sample value for each of the keys, in the same order as the keys.
+    pub values: Vec<String>,
+    /// The inferred type of each of the keys, widened over every instance that was merged into this node.
+    pub types: Vec<ValueType>,
+    /// The inferred type of the text of the element, widened like the types of the keys. Text that is only
+    /// whitespace isn't classified, so elements without text have no type.
+    pub text_type: Option<ValueType>,
+    pub children: Vec<NodeOrToken>,
+    pub parent: Option<usize>,
+    /// The parent of the element in the document. Unlike `parent`, it stays the same when merging moves the
+    /// element to the node its parent was collapsed into.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub origin: Option<usize>,
+    /// The nodes that minimize collapsed into this one. They're no longer in the tree, but each of them is still
+    /// an instance of the element.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub absorbed: Vec<usize>,
+    /// Whether any instance merged into this node had keys or content.
+    pub ever_populated: bool,
+    /// Whether any instance merged into this node had no content, whatever its keys.
+    pub ever_without_content: bool,
+    /// Whether any instance merged into this node had content.
+    pub ever_with_content: bool,
+    /// The comment that directly preceded the element, which usually documents it.
+    pub description: Option<String>,
+    /// Whether the element was written as `<x/>` in the document, instead of `<x></x>` or with content.
+    /// Void elements, like `<br>` in XHTML, count as self-closing.
+    pub self_closing: bool,
+    /// The byte range of the element in the document it was lexed from, from the `<` of its opening tag to the
+    /// `>` of its closing tag. Empty when the graph was built from tokens without a span.
+    pub span: Range<usize>,
+    /// The number of sibling instances that minimize collapsed into this node.
+    pub collapsed: usize,
+    /// The number of instances of the element in the document at this position, including the ones under
+    /// collapsed ancestors.
+    pub occurrences: usize,
+    /// How many of the instances merged into this node have each key. A key with fewer than `occurrences` is
+    /// optional, which only happens when merging.
+    pub key_occurrences: Vec<usize>,
+    /// The smallest number of times the element occurs in one instance of its parent.
+    pub min_occurs: usize,
+    /// The largest number of times the element occurs in one instance of its parent.
+    pub max_occurs: usize,
+}
+
+impl Node {
+    fn new(
+        id: usize,
+        name: String,
+        keys: Vec<String>,
+        values: Vec<String>,
+        parent: Option<usize>,
+    ) -> Node {
+        let (prefix, local_name) = split_qualified_name(&name);
+
+        Node {
+            id,
+            prefix: prefix.map(String::from),
+            local_name: String::from(local_name),
+            name,
+            keys,
+            key_occurrences: vec![1; values.len()],
+            types: values
+                .iter()
+                .map(|value| ValueType::classify(value))
+                .collect(),
+            values,
+            text_type: None,
+            children: Vec::new(),
+            parent,
+            origin: parent,
+            absorbed: Vec::new(),
+            ever_populated: false,
+            ever_without_content: false,
+            ever_with_content: false,
+            description: None,
+            self_closing: false,
+            span: 0..0,
+            collapsed: 0,
+            occurrences: 1,
+            min_occurs: 1,
+            max_occurs: 1,
+        }
+    }
+
+    /// A node is empty when it has no keys and no content.
+    fn is_empty(&self) -> bool {
+        self.keys.is_empty() && !self.has_content()
+    }
+
+    /// Whether this node has children besides whitespace and comments.
+    fn has_content(&self) -> bool {
+        self.children.iter().any(|child| match child {
+            NodeOrToken::N(_) => true,
+            NodeOrToken::T(Token::Text(text) | Token::CData(text)) => !text.trim().is_empty(),
+            NodeOrToken::T(_) => false,
+        })
+    }
+
+    /// Whether this node directly contains text or CDATA that isn't only whitespace.
+    pub fn has_text(&self) -> bool {
+        self.children.iter().any(|child| match child {
+            NodeOrToken::T(Token::Text(text) | Token::CData(text)) => !text.trim().is_empty(),
+            _ => false,
+        })
+    }
+
+    /// Whether this node has both element children and text that isn't only whitespace.
+    pub fn is_mixed(&self) -> bool {
+        self.has_text()
+            && self
+                .children
+                .iter()
+                .any(|child| matches!(child, NodeOrToken::N(_)))
+    }
+
+    /// Whether this node and every instance that was merged into it are empty.
+    pub fn is_always_empty(&self) -> bool {
+        !self.ever_populated && self.is_empty()
+    }
+
+    /// Whether some of the instances merged into this node have content and others don't, like a nullable field.
+    pub fn is_sometimes_empty(&self) -> bool {
+        (self.ever_without_content || !self.has_content())
+            && (self.ever_with_content || self.has_content())
+    }
+
+    /// Two nodes are considered duplicates of each other when they have the same name and keys.
+    /// When merging, the keys don't matter since they are combined.
+    fn signature(&self, strategy: MinimizeStrategy) -> (&str, &[String]) {
+        match strategy {
+            MinimizeStrategy::Merge => (&self.name, &[]),
+            _ => (&self.name, &self.keys),
+        }
+    }
+
+    /// The fraction of the instances merged into this node that have the key, `None` if none of them have it.
+    pub fn key_presence(&self, key: &str) -> Option<f64> {
+        let index = self.keys.iter().position(|k| k == key)?;
+
+        Some(self.key_occurrences[index] as f64 / self.occurrences as f64)
+    }
+
+    /// Whether an ancestor of this node has the same name, like a `<section>` inside a `<section>`.
+    pub fn is_recursive(&self, graph: &Graph) -> bool {
+        let mut parent = self.parent;
+        while let Some(id) = parent {
+            let ancestor = graph.get_node(id);
+            if ancestor.name == self.name {
+                return true;
+            }
+            parent = ancestor.parent;
+        }
+
+        false
+    }
+
+    pub fn print(&self, graph: &Graph, config: &Config) -> String {
+        /// The remaining work of the print, popped in the order it's printed.
+        /// Tokens and end tags know whether their element is printed as a block, one child per line.
+        enum Step<'a> {
+            Open(usize, usize),
+            Token(&'a Token, usize, bool),
+            Type(ValueType),
+            Code(bool),
+            Omitted(usize, usize, bool),
+            Close(usize, usize, bool, bool),
+        }
+
+        // The names of the ancestors that are being printed, to tell when an element is recursive.
+        let mut ancestors = HashMap::<&str, usize>::new();
+        let mut parent = self.parent;
+        while let Some(id) = parent {
+            let ancestor = graph.get_node(id);
+            *ancestors.entry(&ancestor.name).or_default() += 1;
+            parent = ancestor.parent;
+        }
+
+        let mut res = String::new();
+        let mut stack = vec![Step::Open(self.id, 0)];
+        while let Some(step) = stack.pop() {
+            match step {
+                Step::Open(id, depth) => {
+                    let node = graph.get_node(id);
+                    let recursive = ancestors.get(node.name.as_str()).is_some_and(|n| *n > 0);
+                    if config.indent > 0 {
+                        new_line(config, depth, &mut res);
+                    }
+                    if node.open_tag(config, depth, recursive, &mut res) {
+                        *ancestors.entry(&node.name).or_default() += 1;
+                        let elements = node
+                            .children
+                            .iter()
+                            .filter(|child| matches!(child, NodeOrToken::N(_)))
+                            .count();
+                        let block = config.indent > 0 && elements > 0;
+                        stack.push(Step::Close(id, depth, block, recursive));
+
+                        // The elements past the maximum are left out together with the whitespace in front of them.
+                        // The first of them is replaced by a comment, so the whitespace before the end tag is kept.
+                        let shown = config.max_children.unwrap_or(elements).min(elements);
+                        // The text of a leaf is replaced by its type once, where the first text is, without the
+                        // whitespace around it.
+                        let typed = node.text_type.is_some() && config.types && elements == 0;
+                        let mut text_type = node.text_type.filter(|_| typed);
+                        let code = config.is_code_element(&node.name);
+                        let mut steps = Vec::new();
+                        let mut seen = 0;
+                        for child in &node.children {
+                            match child {
+                                NodeOrToken::N(_) if seen > shown => {
+                                    while let Some(Step::Token(
+                                        Token::Whitespace | Token::Newline,
+                                        _,
+                                        _,
+                                    )) = steps.last()
+                                    {
+                                        steps.pop();
+                                    }
+                                }
+                                NodeOrToken::N(_) if seen == shown => {
+                                    seen += 1;
+                                    steps.push(Step::Omitted(elements - shown, depth + 1, block));
+                                }
+                                NodeOrToken::N(child_id) => {
+                                    seen += 1;
+                                    steps.push(Step::Open(*child_id, depth + 1));
+                                }
+                                NodeOrToken::T(Token::Text(_) | Token::CData(_))
+                                    if config.no_text => {}
+                                // The code in an element like `<script>` is replaced by an ellipsis.
+                                NodeOrToken::T(Token::Text(text) | Token::CData(text))
+                                    if code && !text.trim().is_empty() =>
+                                {
+                                    steps.push(Step::Code(matches!(
+                                        child,
+                                        NodeOrToken::T(Token::CData(_))
+                                    )))
+                                }
+                                NodeOrToken::T(Token::Text(text)) if typed => {
+                                    if !text.trim().is_empty() {
+                                        steps.extend(text_type.take().map(Step::Type));
+                                    }
+                                }
+                                NodeOrToken::T(Token::Whitespace | Token::Newline)
+                                    if typed || code && elements == 0 => {}
+                                NodeOrToken::T(token) => {
+                                    steps.push(Step::Token(token, depth + 1, block))
+                                }
+                            }
+                        }
+                        stack.extend(steps.into_iter().rev());
+                    } else {
+                        node.annotate(config, recursive, &mut res);
+                    }
+                }
+                // When indenting, the whitespace of the document is replaced by the indentation.
+                Step::Token(token, depth, block) if config.indent > 0 => {
+                    let mut content = String::new();
+                    print_token(token, config, &mut content);
+                    let content = content.trim();
+                    if !content.is_empty() {
+                        if block {
+                            new_line(config, depth, &mut res);
+                        }
+                        res.push_str(content);
+                    }
+                }
+                Step::Token(token, _, _) => print_token(token, config, &mut res),
+                Step::Type(text_type) => {
+                    res.push_str(&paint(text_type.placeholder(), TEXT_STYLE, config))
+                }
+                Step::Code(cdata) => {
+                    let code = paint("…", TEXT_STYLE, config);
+                    if cdata {
+                        res.push_str(&format!("<![CDATA[{}]]>", code));
+                    } else {
+                        res.push_str(&code);
+                    }
+                }
+                Step::Omitted(count, depth, block) => {
+                    if block {
+                        new_line(config, depth, &mut res);
+                    }
+                    let children = if count == 1 { "child" } else { "children" };
+                    res.push_str(&format!("<!-- {} more {} omitted -->", count, children));
+                }
+                Step::Close(id, depth, block, recursive) => {
+                    let node = graph.get_node(id);
+                    if let Some(count) = ancestors.get_mut(node.name.as_str()) {
+                        *count -= 1;
+                    }
+                    if block {
+                        new_line(config, depth, &mut res);
+                    }
+                    res.push_str(&format!("</{}>", paint(&node.name, ELEMENT_STYLE, config)));
+                    node.annotate(config, recursive, &mut res);
+                }
+            }
+        }
+
+        res
+    }
+
+    /// Prints the start tag of the node as if it is `depth` levels below the node the print started at,
+    /// returning whether its children and end tag still have to be printed.
+    /// Once the maximum depth of the config is reached, the children are left out.
+    /// The children of a recursive node are left out as well, so they aren't expanded again.
+    fn open_tag(&self, config: &Config, depth: usize, recursive: bool, res: &mut String) -> bool {
+        res.push_str(&format!("<{}", paint(&self.name, ELEMENT_STYLE, config)));
+        let mut keys: Vec<_> = self
+            .keys
+            .iter()
+            .zip(&self.values)
+            .zip(&self.types)
+            .zip(&self.key_occurrences)
+            .collect();
+        if config.sort_attributes {
+            keys.sort_by_key(|(((key, _), _), _)| *key);
+        }
+        for (((key, value), value_type), count) in keys {
+            // Keys that only some of the merged instances have are marked as optional.
+            let optional = if !config.plain_keys && *count < self.occurrences {
+                "?"
+            } else {
+                ""
+            };
+            let value = if config.strip_values {
+                ""
+            } else if config.types {
+                value_type.placeholder()
+            } else {
+                &escape_attribute(&truncate(value, config.max_value_len))
+            };
+            res.push_str(&format!(
+                " {}{}=\"{}\"",
+                paint(key, ATTRIBUTE_STYLE, config),
+                optional,
+                value
+            ));
+        }
+
+        let truncated = (recursive || config.depth.is_some_and(|max_depth| depth >= max_depth))
+            && self
+                .children
+                .iter()
+                .any(|child| matches!(child, NodeOrToken::N(_)));
+
+        // An element without children keeps the form it has in the document, `<x/>` or `<x></x>`. Without text,
+        // an element that only has text and whitespace is printed as empty.
+        let empty = if config.no_text {
+            self.children.iter().all(|child| {
+                matches!(
+                    child,
+                    NodeOrToken::T(
+                        Token::Text(_) | Token::CData(_) | Token::Whitespace | Token::Newline
+                    )
+                )
+            })
+        } else {
+            self.children.is_empty() && self.self_closing
+        };
+
+        if truncated {
+            res.push_str(" .../>");
+        } else if empty {
+            res.push_str("/>");
+        } else {
+            res.push('>');
+            return true;
+        }
+
+        false
+    }
+
+    /// Prints the comments and counts that follow the element.
+    fn annotate(&self, config: &Config, recursive: bool, res: &mut String) {
+        if recursive {
+            res.push_str(" <!-- recursive -->");
+        }
+        if config.counts && self.occurrences > 1 {
+            res.push_str(&format!(" (×{})", self.occurrences));
+        }
+        if config.cardinality {
+            let suffix = cardinality(self.min_occurs, self.max_occurs);
+            if !suffix.is_empty() {
+                res.push_str(&format!(" <!-- cardinality: {} -->", suffix));
+            }
+            if self.is_sometimes_empty() {
+                res.push_str(" <!-- sometimes empty -->");
+            }
+        }
+        if config.show_mixed && self.is_mixed() {
+            res.push_str(" <!-- mixed content -->");
+        }
+        if config.verbose && self.collapsed > 0 {
+            let occurrences = if self.collapsed == 1 {
+                "occurrence"
+            } else {
+                "occurrences"
+            };
+            res.push_str(&format!(
+                " <!-- {} {} collapsed -->",
+                self.collapsed, occurrences
+            ));
+        }
+        if config.verbose && self.is_always_empty() {
+            res.push_str(" <!-- always empty -->");
+        }
+        if config.verbose {
+            for key in &self.keys {
+                let presence = self.key_presence(key).unwrap_or(1.0);
+                if presence < 1.0 {
+                    res.push_str(&format!(
+                        " <!-- {} in {:.0}% of instances -->",
+                        key,
+                        presence * 100.0
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Renders the overview of just this node's subtree.
+    /// For the synthetic root only its children are rendered, so the result never contains the `<0>` wrapper.
+    /// Every top-level element starts on its own line, so fragments with several of them stay readable.
+    pub fn to_string_pretty(&self, graph: &Graph, config: &Config) -> String {
+        if self.parent.is_some() {
+            return self.print(graph, config);
+        }
+
+        let mut res = String::new();
+        for child in &self.children {
+            match child {
+                NodeOrToken::N(id) => {
+                    if !res.is_empty() && !res.ends_with('\n') {
+                        res.push('\n');
+                    }
+                    res.push_str(&graph.get_node(*id).print(graph, config));
+                }
+                NodeOrToken::T(Token::Newline)
+                    if config.indent == 0 && !res.is_empty() && !res.ends_with('\n') =>
+                {
+                    res.push('\n')
+                }
+                NodeOrToken::T(_) => (),
+            }
+        }
+
+        res
+    }
+}
+
+/// Formats the start tag of the node with its attributes, or an empty tag when it was self-closing.
+/// The children live in the graph, print the node with [`Node::print`] to include them.
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<{}", self.name)?;
+        for (key, value) in self.keys.iter().zip(&self.values) {
+            write!(f, " {}=\"{}\"", key, escape_attribute(value))?;
+        }
+
+        f.write_str(if self.children.is_empty() && self.self_closing {
+            "/>"
+        } else {
+            ">"
+        })
+    }
+}
+
+/// Arena of all nodes in the document. Node 0 is a synthetic root that holds the top-level elements.
+///
+/// With the `serde` feature the graph serializes as this arena: a flat list of nodes where `parent` and
+/// the `N` children are indices into `nodes`, rather than a nested tree.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Graph {
+    nodes: Vec<Node>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    current: usize,
+    xml_declaration: Option<XmlDeclaration>,
+    /// The distinct namespace declarations that were left out of the attributes, like `("xmlns:soap", uri)`.
+    namespaces: Vec<(String, String)>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    warnings: Vec<String>,
+}
+
+impl Default for Graph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Graph {
+    pub fn new() -> Graph {
+        Graph {
+            nodes: vec![Node::new(
+                0,
+                String::from("0"),
+                Vec::new(),
+                Vec::new(),
+                None,
+            )],
+            current: 0,
+            xml_declaration: None,
+            namespaces: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    pub fn get_node(&self, id: usize) -> &Node {
+        &self.nodes[id]
+    }
+
+    /// Walks the elements that are reachable from the root in document order, without the synthetic root.
+    pub fn iter(&self) -> impl Iterator<Item = &Node> {
+        let mut stack: Vec<usize> = self.child_ids(0).into_iter().rev().collect();
+        std::iter::from_fn(move || {
+            let id = stack.pop()?;
+            stack.extend(self.child_ids(id).into_iter().rev());
+            Some(self.get_node(id))
+        })
+    }
+
+    /// The element children of the node, leaving out the tokens between them.
+    pub fn children_of(&self, id: usize) -> impl Iterator<Item = &Node> {
+        self.child_ids(id).into_iter().map(|id| self.get_node(id))
+    }
+
+    /// The declaration of the document, if it started with a valid `<?xml ... ?>`.
+    pub fn xml_declaration(&self) -> Option<XmlDeclaration> {
+        self.xml_declaration.clone()
+    }
+
+    pub fn set_xml_declaration(&mut self, declaration: Option<XmlDeclaration>) {
+        self.xml_declaration = declaration;
+    }
+
+    /// The namespace declarations that were hidden from the attributes, in the order they first appear.
+    pub fn namespaces(&self) -> &[(String, String)] {
+        &self.namespaces
+    }
+
+    pub fn add_namespace(&mut self, key: String, uri: String) {
+        let namespace = (key, uri);
+        if !self.namespaces.contains(&namespace) {
+            self.namespaces.push(namespace);
+        }
+    }
+
+    /// Recoverable problems that were found while building the graph.
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    pub fn warn(&mut self, warning: String) {
+        self.warnings.push(warning);
+    }
+
+    /// The node that new nodes and tokens are added to.
+    pub fn current_node(&self) -> &Node {
+        self.get_node(self.current)
+    }
+
+    /// Adds a node as child of the current node and makes it the current node.
+    pub fn add_node(&mut self, name: String, keys: Vec<String>, values: Vec<String>) -> usize {
+        let id = self.nodes.len();
+        self.nodes
+            .push(Node::new(id, name, keys, values, Some(self.current)));
+        self.nodes[self.current].children.push(NodeOrToken::N(id));
+        self.current = id;
+
+        id
+    }
+
+    pub fn set_self_closing(&mut self, id: usize) {
+        self.nodes[id].self_closing = true;
+    }
+
+    pub fn set_span(&mut self, id: usize, span: Range<usize>) {
+        self.nodes[id].span = span;
+    }
+
+    pub fn set_description(&mut self, id: usize, description: String) {
+        self.nodes[id].description = Some(description);
+    }
+
+    pub fn add_token(&mut self, token: Token) {
+        let node = &mut self.nodes[self.current];
+        if let Token::Text(text) = &token
+            && !text.trim().is_empty()
+        {
+            let text_type = ValueType::classify(&decode_entities(text));
+            node.text_type = Some(node.text_type.map_or(text_type, |t| t.widen(text_type)));
+        }
+        node.children.push(NodeOrToken::T(token));
+    }
+
+    /// Ends the span of the current node at the byte offset and makes its parent the current node.
+    pub fn close_current(&mut self, end: usize) {
+        if let Some(parent) = self.nodes[self.current].parent {
+            self.nodes[self.current].span.end = end;
+            self.current = parent;
+        }
+    }
+
+    /// Collapses sibling nodes with the same signature into one, keeping the node with the most children.
+    /// Groups with fewer instances than the collapse threshold of the config are left as they are.
+    pub fn minimize(&mut self, config: &Config) {
+        // Every node is minimized after its descendants, without recursing so deep documents fit on the stack.
+        let mut order = vec![0];
+        let mut index = 0;
+        while index < order.len() {
+            order.extend(self.child_ids(order[index]));
+            index += 1;
+        }
+        for id in order.into_iter().rev() {
+            self.minimize_node(id, config);
+        }
+    }
+
+    /// Collapses the children of the node, which have to be minimized already.
+    fn minimize_node(&mut self, id: usize, config: &Config) {
+        let child_ids = self.child_ids(id);
+
+        // Group the children by signature, in the order they first appear.
+        let mut groups = Vec::<Vec<usize>>::new();
+        let mut group_of = HashMap::<(&str, &[String]), usize>::new();
+        for child_id in child_ids {
+            let signature = self.nodes[child_id].signature(config.minimize_strategy);
+            match group_of.get(&signature) {
+                Some(&index) => groups[index].push(child_id),
+                None => {
+                    group_of.insert(signature, groups.len());
+                    groups.push(vec![child_id]);
+                }
+            }
+        }
+
+        let parent = if id == 0 {
+            String::from("the root")
+        } else {
+            format!("<{}>", self.nodes[id].name)
+        };
+        let mut survivors = Vec::<usize>::new();
+        for group in groups {
+            if group.len() < config.collapse_threshold {
+                if group.len() > 1 {
+                    config.log(LogLevel::Debug, || {
+                        format!(
+                            "Kept {} <{}> in {}, fewer than the collapse threshold of {}",
+                            group.len(),
+                            self.nodes[group[0]].name,
+                            parent,
+                            config.collapse_threshold
+                        )
+                    });
+                }
+                survivors.extend(group);
+                continue;
+            }
+
+            let first = group[0];
+            let mut survivor = first;
+            let mut ever_populated = false;
+            let mut description = None;
+            let mut collapsed = group.len() - 1;
+            let mut types = self.nodes[first].types.clone();
+            for &child_id in &group {
+                let child = &self.nodes[child_id];
+                // Merged nodes can have different keys, those are widened by key when absorbing.
+                if child.keys == self.nodes[first].keys {
+                    for (value_type, other) in types.iter_mut().zip(&child.types) {
+                        *value_type = value_type.widen(*other);
+                    }
+                }
+                ever_populated |= child.ever_populated || !child.is_empty();
+                description = description.or(child.description.clone());
+                collapsed += child.collapsed;
+                if config.minimize_strategy == MinimizeStrategy::MostChildren
+                    && child.children.len() > self.nodes[survivor].children.len()
+                {
+                    survivor = child_id;
+                }
+            }
+            self.nodes[survivor].ever_populated = ever_populated;
+            self.nodes[survivor].description = description;
+            self.nodes[survivor].collapsed = collapsed;
+            // The sample values are the ones of the first instance in the document.
+            self.nodes[survivor].values = self.nodes[first].values.clone();
+            self.nodes[survivor].types = types;
+            self.nodes[survivor].min_occurs = group.len();
+            self.nodes[survivor].max_occurs = group.len();
+            if group.len() > 1 {
+                config.log(LogLevel::Debug, || {
+                    format!(
+                        "Collapsed {} <{}> in {} into node {}",
+                        group.len(),
+                        self.nodes[survivor].name,
+                        parent,
+                        survivor
+                    )
+                });
+            }
+            for child_id in group {
+                if child_id != survivor {
+                    self.absorb(survivor, child_id, config.minimize_strategy);
+                }
+            }
+            survivors.push(survivor);
+        }
+
+        // The whitespace before a collapsed sibling goes with it, so no blank lines are left where it was.
+        let survivors: HashSet<usize> = survivors.into_iter().collect();
+        let mut children = Vec::with_capacity(self.nodes[id].children.len());
+        let mut whitespace = Vec::new();
+        for child in std::mem::take(&mut self.nodes[id].children) {
+            match child {
+                NodeOrToken::T(Token::Whitespace | Token::Newline) => whitespace.push(child),
+                NodeOrToken::N(child_id) if !survivors.contains(&child_id) => whitespace.clear(),
+                child => {
+                    children.append(&mut whitespace);
+                    children.push(child);
+                }
+            }
+        }
+        children.append(&mut whitespace);
+        self.nodes[id].children = children;
+    }
+
+    /// Adds the statistics of a collapsed node and its descendants to the matching nodes of the survivor.
+    /// The occurrences are summed, the number of times a child occurs per instance is widened to cover both.
+    /// When merging, the keys and children that only the collapsed node has are added to the survivor.
+    fn absorb(&mut self, survivor: usize, collapsed: usize, strategy: MinimizeStrategy) {
+        // The pairs of matching descendants are independent of each other, so a stack can replace recursion.
+        let mut pairs = vec![(survivor, collapsed)];
+        while let Some((survivor, collapsed)) = pairs.pop() {
+            self.nodes[survivor].absorbed.push(collapsed);
+            self.nodes[survivor].occurrences += self.nodes[collapsed].occurrences;
+            // Whether the instances are empty is recorded before merging adds keys and children to the survivor.
+            for id in [survivor, collapsed] {
+                let node = &self.nodes[id];
+                let has_content = node.has_content();
+                let (populated, without, with) = (
+                    node.ever_populated || !node.is_empty(),
+                    node.ever_without_content || !has_content,
+                    node.ever_with_content || has_content,
+                );
+                self.nodes[survivor].ever_populated |= populated;
+                self.nodes[survivor].ever_without_content |= without;
+                self.nodes[survivor].ever_with_content |= with;
+            }
+            if let Some(other) = self.nodes[collapsed].text_type {
+                let text_type = &mut self.nodes[survivor].text_type;
+                *text_type = Some(text_type.map_or(other, |t| t.widen(other)));
+            }
+
+            // Only the attributes the survivor doesn't have yet are copied, not the whole collapsed node.
+            let [node, other] = self
+                .nodes
+                .get_disjoint_mut([survivor, collapsed])
+                .expect("A node isn't absorbed into itself");
+            if strategy == MinimizeStrategy::Merge {
+                for (index, key) in other.keys.iter().enumerate() {
+                    let (value_type, count) = (other.types[index], other.key_occurrences[index]);
+                    match node.keys.iter().position(|k| k == key) {
+                        Some(index) => {
+                            node.types[index] = node.types[index].widen(value_type);
+                            node.key_occurrences[index] += count;
+                        }
+                        None => {
+                            node.keys.push(key.clone());
+                            node.values.push(other.values[index].clone());
+                            node.types.push(value_type);
+                            node.key_occurrences.push(count);
+                        }
+                    }
+                }
+            } else {
+                // Without merging, the keys of both nodes are the same.
+                for (index, count) in other.key_occurrences.iter().enumerate() {
+                    node.types[index] = node.types[index].widen(other.types[index]);
+                    node.key_occurrences[index] += count;
+                }
+            }
+
+            // Every child of the survivor is paired with the first unpaired child of the collapsed node with the
+            // same signature.
+            let collapsed_children = self.child_ids(collapsed);
+            let mut unpaired = HashMap::<(&str, &[String]), VecDeque<usize>>::new();
+            for &child_id in &collapsed_children {
+                unpaired
+                    .entry(self.nodes[child_id].signature(strategy))
+                    .or_default()
+                    .push_back(child_id);
+            }
+            let mut matches = Vec::<(usize, Option<usize>)>::new();
+            for survivor_child in self.child_ids(survivor) {
+                let matching = unpaired
+                    .get_mut(&self.nodes[survivor_child].signature(strategy))
+                    .and_then(VecDeque::pop_front);
+                matches.push((survivor_child, matching));
+            }
+            let mut unmatched: HashSet<usize> = unpaired.into_values().flatten().collect();
+
+            for (survivor_child, matching) in matches {
+                match matching {
+                    Some(child_id) => {
+                        let (min_occurs, max_occurs) = (
+                            self.nodes[child_id].min_occurs,
+                            self.nodes[child_id].max_occurs,
+                        );
+                        let node = &mut self.nodes[survivor_child];
+                        node.min_occurs = node.min_occurs.min(min_occurs);
+                        node.max_occurs = node.max_occurs.max(max_occurs);
+                        pairs.push((survivor_child, child_id));
+                    }
+                    // The collapsed instance doesn't have this child at all.
+                    None => self.nodes[survivor_child].min_occurs = 0,
+                }
+            }
+
+            if strategy == MinimizeStrategy::Merge {
+                // Children that the survivor doesn't have didn't occur in its instance. They go before the
+                // whitespace in front of the survivor's closing tag, together with the whitespace in front of them.
+                let mut position = self.nodes[survivor].children.len()
+                    - trailing_layout(&self.nodes[survivor].children);
+                for child_id in collapsed_children
+                    .into_iter()
+                    .filter(|child_id| unmatched.remove(child_id))
+                {
+                    self.nodes[child_id].min_occurs = 0;
+                    self.nodes[child_id].parent = Some(survivor);
+                    let siblings = &self.nodes[collapsed].children;
+                    let index = siblings
+                        .iter()
+                        .position(|child| *child == NodeOrToken::N(child_id))
+                        .expect("A child is among the children of its parent");
+                    let layout = trailing_layout(&siblings[..index]);
+                    let merged: Vec<NodeOrToken> = siblings[index - layout..=index].to_vec();
+                    let count = merged.len();
+                    self.nodes[survivor]
+                        .children
+                        .splice(position..position, merged);
+                    position += count;
+                }
+            }
+        }
+    }
+
+    fn child_ids(&self, id: usize) -> Vec<usize> {
+        self.nodes[id]
+            .children
+            .iter()
+            .filter_map(|child| match child {
+                NodeOrToken::N(child_id) => Some(*child_id),
+                NodeOrToken::T(_) => None,
+            })
+            .collect()
+    }
+
+    /// Prints the overview of the document, without the synthetic root.
+    pub fn print(&self, config: &Config) -> String {
+        let mut res = String::new();
+        if let Some(declaration) = self.xml_declaration() {
+            res.push_str(&declaration.to_string());
+        }
+        // The hidden namespace declarations are summarized once, before the elements.
+        if !self.namespaces.is_empty() {
+            if !res.is_empty() {
+                res.push('\n');
+            }
+            let namespaces: Vec<String> = self
+                .namespaces
+                .iter()
+                .map(|(key, uri)| format!("{}=\"{}\"", key, escape_attribute(uri)))
+                .collect();
+            res.push_str(&format!("<!-- {} -->", namespaces.join(" ")));
+        }
+
+        let mut body = self.get_node(0).to_string_pretty(self, config);
+        // A document with only a prolog, like a declaration and comments, has no elements to print.
+        if config.verbose && self.child_ids(0).is_empty() {
+            body = String::from("<!-- no root element found -->");
+        }
+        if !res.is_empty() && !body.is_empty() {
+            res.push('\n');
+        }
+        res.push_str(&body);
+
+        res
+    }
+
+    /// The elements with the given name that aren't inside another element with that name, in document order.
+    pub fn find_elements(&self, name: &str) -> Vec<usize> {
+        let mut ids = Vec::new();
+        let mut stack: Vec<usize> = self.child_ids(0).into_iter().rev().collect();
+        while let Some(id) = stack.pop() {
+            if self.get_node(id).name == name {
+                ids.push(id);
+            } else {
+                stack.extend(self.child_ids(id).into_iter().rev());
+            }
+        }
+
+        ids
+    }
+
+    /// Every element with the given name in document order, including the ones inside another match.
+    pub fn find_by_name(&self, name: &str) -> Vec<&Node> {
+        self.iter().filter(|node| node.name == name).collect()
+    }
+
+    /// The elements at a slash-separated path from a top-level element, like `catalog/book/author`, in document
+    /// order. A leading `/` is allowed, so the paths of [`Graph::to_paths`] can be used as well.
+    pub fn find_by_path(&self, path: &str) -> Vec<&Node> {
+        let mut nodes = vec![self.get_node(0)];
+        for name in path.strip_prefix('/').unwrap_or(path).split('/') {
+            nodes = nodes
+                .into_iter()
+                .flat_map(|node| self.children_of(node.id))
+                .filter(|node| node.name == name)
+                .collect();
+        }
+
+        nodes
+    }
+
+    /// Removes every element that isn't at one of the paths, see [`Graph::find_by_path`], keeping the subtrees of
+    /// the elements that are and the ancestors that lead to them. A path that matches nothing is warned about.
+    pub fn keep_only(&mut self, paths: &[String]) {
+        let mut matched = HashSet::<usize>::new();
+        for path in paths {
+            let ids: Vec<usize> = self.find_by_path(path).iter().map(|node| node.id).collect();
+            if ids.is_empty() {
+                self.warn(format!("No element is at the path {}", path));
+            }
+            matched.extend(ids);
+        }
+        let mut ancestors = HashSet::<usize>::new();
+        for &id in &matched {
+            let mut parent = self.nodes[id].parent;
+            while let Some(id) = parent {
+                if !ancestors.insert(id) {
+                    break;
+                }
+                parent = self.nodes[id].parent;
+            }
+        }
+
+        // Only the children of the ancestors are pruned, the matched elements keep all of theirs.
+        // Like in minimize, the whitespace before a removed element goes with it.
+        for &id in ancestors.difference(&matched) {
+            let mut children = Vec::with_capacity(self.nodes[id].children.len());
+            let mut whitespace = Vec::new();
+            for child in std::mem::take(&mut self.nodes[id].children) {
+                match child {
+                    NodeOrToken::T(Token::Whitespace | Token::Newline) => whitespace.push(child),
+                    NodeOrToken::N(child_id)
+                        if !matched.contains(&child_id) && !ancestors.contains(&child_id) =>
+                    {
+                        whitespace.clear()
+                    }
+                    child => {
+                        children.append(&mut whitespace);
+                        children.push(child);
+                    }
+                }
+            }
+            children.append(&mut whitespace);
+            self.nodes[id].children = children;
+        }
+        if matched.is_empty() {
+            self.nodes[0].children.clear();
+        }
+    }
+
+    /// Prints the subtrees of the elements with the given name, one per line, or `None` if there are none.
+    /// A name with a `/` is a path, see [`Graph::find_by_path`], which only matches the elements at that path.
+    /// Minimize only collapses siblings, so subtrees under different parents that only differ in their
+    /// attribute values are left out here, keeping the first one.
+    pub fn print_element(&self, name: &str, config: &Config) -> Option<String> {
+        let shape_config = Config {
+            strip_values: true,
+            ..config.clone()
+        };
+        let mut shapes = Vec::<String>::new();
+        let mut subtrees = Vec::<String>::new();
+        let ids = if name.contains('/') {
+            self.find_by_path(name)
+                .into_iter()
+                .map(|node| node.id)
+                .collect()
+        } else {
+            self.find_elements(name)
+        };
+        for id in ids {
+            let node = self.get_node(id);
+            let shape = node.print(self, &shape_config);
+            if !shapes.contains(&shape) {
+                shapes.push(shape);
+                subtrees.push(node.print(self, config));
+            }
+        }
+
+        (!subtrees.is_empty()).then(|| subtrees.join("\n"))
+    }
+
+    /// Serializes the graph as MessagePack, tagged with [`MSGPACK_FORMAT_VERSION`].
+    #[cfg(feature = "serde")]
+    pub fn to_msgpack(&self) -> Result<Vec<u8>, rmp_serde::encode::Error> {
+        #[derive(Serialize)]
+        struct Snapshot<'a> {
+            format_version: u32,
+            graph: &'a Graph,
+        }
+
+        rmp_serde::to_vec_named(&Snapshot {
+            format_version: MSGPACK_FORMAT_VERSION,
+            graph: self,
+        })
+    }
+}
+
+/// Formats the overview with the default config, use [`Graph::print`] for other settings.
+impl fmt::Display for Graph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.print(&Config::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::converter::parser::{lex_graph, lex_tokens};
+
+    fn graph_from(xml: &str) -> Graph {
+        lex_graph(lex_tokens(xml).unwrap(), &Config::default()).unwrap()
+    }
+
+    #[test]
+    fn test_print_graph() {
+        let graph = graph_from("<tag><child key=\"1\">Content</child><empty/></tag>");
+
+        assert_eq!(
+            graph.print(&Config::default()),
+            "<tag><child key=\"1\">Content</child><empty/></tag>"
+        );
+    }
+
+    #[test]
+    fn test_iter() {
+        let graph = graph_from("<a><b><c/></b>text<d/></a><e/>");
+        let names = |nodes: Vec<&Node>| -> Vec<String> {
+            nodes.into_iter().map(|node| node.name.clone()).collect()
+        };
+
+        assert_eq!(names(graph.iter().collect()), vec!["a", "b", "c", "d", "e"]);
+        assert_eq!(names(graph.children_of(1).collect()), vec!["b", "d"]);
+        assert_eq!(names(graph.children_of(0).collect()), vec!["a", "e"]);
+    }
+
+    #[test]
+    fn test_display() {
+        let graph = graph_from("<tag><child key=\"1\">Content</child><empty/></tag>");
+
+        assert_eq!(graph.to_string(), graph.print(&Config::default()));
+        assert_eq!(graph.get_node(1).to_string(), "<tag>");
+        assert_eq!(graph.get_node(2).to_string(), "<child key=\"1\">");
+        assert_eq!(graph.get_node(3).to_string(), "<empty/>");
+
+        let graph = graph_from("<a title='say \"hi\" &amp; &lt;bye&gt;'/>");
+        assert_eq!(
+            graph.get_node(1).to_string(),
+            "<a title=\"say &quot;hi&quot; &amp; &lt;bye>\"/>"
+        );
+    }
+
+    #[test]
+    fn test_print_recursive() {
+        let graph = graph_from(
+            "<doc><section id=\"1\"><title/><section id=\"2\"><title/><section/></section></section></doc>",
+        );
+
+        assert_eq!(
+            graph.print(&Config::default()),
+            "<doc><section id=\"1\"><title/><section id=\"2\" .../> <!-- recursive --></section></doc>"
+        );
+    }
+
+    #[test]
+    fn test_print_recursive_text() {
+        let graph = graph_from("<s><s>t</s></s>");
+
+        assert_eq!(
+            graph.print(&Config::default()),
+            "<s><s>t</s> <!-- recursive --></s>"
+        );
+    }
+
+    #[test]
+    fn test_print_strip_values() {
+        let graph = graph_from("<item id=\"42\" status=\"active\"/>");
+
+        assert_eq!(
+            graph.print(&Config {
+                strip_values: true,
+                ..Config::default()
+            }),
+            "<item id=\"\" status=\"\"/>"
+        );
+    }
+
+    #[test]
+    fn test_minimize_keeps_first_values() {
+        let mut graph = graph_from("<list><item id=\"1\"/><item id=\"2\"><name/></item></list>");
+        graph.minimize(&Config::default());
+
+        assert_eq!(
+            graph.print(&Config::default()),
+            "<list><item id=\"1\"><name/></item></list>"
+        );
+    }
+
+    #[test]
+    fn test_classify_value() {
+        assert_eq!(ValueType::classify("42"), ValueType::Int);
+        assert_eq!(ValueType::classify("-7"), ValueType::Int);
+        assert_eq!(ValueType::classify("19.99"), ValueType::Float);
+        assert_eq!(ValueType::classify("true"), ValueType::Bool);
+        assert_eq!(ValueType::classify("2024-01-31"), ValueType::Date);
+        assert_eq!(ValueType::classify("2024-01-31T12:00:00Z"), ValueType::Date);
+        assert_eq!(ValueType::classify("inf"), ValueType::String);
+        assert_eq!(ValueType::classify("active"), ValueType::String);
+        assert_eq!(ValueType::classify(""), ValueType::String);
+    }
+
+    #[test]
+    fn test_print_types() {
+        let mut graph = graph_from(
+            "<list>\
+                <item id=\"1\" price=\"3\" enabled=\"true\" when=\"2024-01-31\" code=\"7\"/>\
+                <item id=\"2\" price=\"3.5\" enabled=\"false\" when=\"2024-02-01\" code=\"x\"/>\
+            </list>",
+        );
+        graph.minimize(&Config::default());
+
+        assert_eq!(
+            graph.print(&Config {
+                types: true,
+                ..Config::default()
+            }),
+            "<list><item id=\"{int}\" price=\"{float}\" enabled=\"{bool}\" when=\"{date}\" code=\"{string}\"/></list>"
+        );
+    }
+
+    #[test]
+    fn test_print_nested_types() {
+        let config = Config {
+            types: true,
+            ..Config::default()
+        };
+        for strategy in [
+            MinimizeStrategy::MostChildren,
+            MinimizeStrategy::First,
+            MinimizeStrategy::Merge,
+        ] {
+            let mut graph = graph_from("<r><a><b v=\"1\"/></a><a><b v=\"x\"/></a></r>");
+            graph.minimize(&Config {
+                minimize_strategy: strategy,
+                ..Config::default()
+            });
+
+            assert_eq!(graph.print(&config), "<r><a><b v=\"{string}\"/></a></r>");
+        }
+    }
+
+    #[test]
+    fn test_print_text_types() {
+        let mut graph = graph_from(
+            "<list>\
+                <item><price> 19.99 </price><count>3</count><sold>true</sold><code>7</code><note>  </note></item>\
+                <item><price>5</price><count>4<!-- more -->2</count><sold>false</sold><code>x</code></item>\
+                <item><count/></item>\
+            </list>",
+        );
+        graph.minimize(&Config {
+            minimize_strategy: MinimizeStrategy::Merge,
+            ..Config::default()
+        });
+
+        assert_eq!(
+            graph.print(&Config {
+                types: true,
+                ..Config::default()
+            }),
+            "<list><item><price>{float}</price><count>{int}</count><sold>{bool}</sold><code>{string}</code><note>  </note></item></list>"
+        );
+        assert_eq!(graph.find_by_name("note")[0].text_type, None);
+    }
+
+    #[test]
+    fn test_print_no_text() {
+        let graph = graph_from(
+            "<a>Intro <b> Bold </b><c><![CDATA[x]]></c> and <d>\n  <e/>\n  tail\n</d><!-- note --></a>",
+        );
+
+        assert_eq!(
+            graph.print(&Config {
+                no_text: true,
+                ..Config::default()
+            }),
+            "<a><b/><c/> <d>\n  <e/>\n  </d></a>"
+        );
+    }
+
+    #[test]
+    fn test_print_cdata() {
+        let graph = graph_from("<script><![CDATA[a < b]]></script>");
+
+        assert_eq!(
+            graph.print(&Config::default()),
+            "<script><![CDATA[a < b]]></script>"
+        );
+    }
+
+    #[test]
+    fn test_print_mid_tree_node() {
+        let graph = graph_from("<a>\n  <b key=\"1\">\n    <c>Text</c>\n  </b>\n  <d/>\n</a>\n");
+        let node = graph.nodes.iter().find(|node| node.name == "b").unwrap();
+
+        assert_eq!(
+            node.to_string_pretty(&graph, &Config::default()),
+            "<b key=\"1\">\n    <c>Text</c>\n  </b>"
+        );
+        assert_eq!(
+            graph
+                .get_node(0)
+                .to_string_pretty(&graph, &Config::default()),
+            "<a>\n  <b key=\"1\">\n    <c>Text</c>\n  </b>\n  <d/>\n</a>\n"
+        );
+    }
+
+    #[test]
+    fn test_print_root_without_elements() {
+        // A root without children would render as `<0/>`, which must never show up.
+        for graph in [Graph::new(), graph_from("  \n<!-- only a comment -->\n")] {
+            assert_eq!(
+                graph
+                    .get_node(0)
+                    .to_string_pretty(&graph, &Config::default()),
+                ""
+            );
+            assert_eq!(graph.print(&Config::default()), "");
+        }
+
+        let mut graph = graph_from("<a/><a/><a/>");
+        graph.minimize(&Config::default());
+        assert_eq!(graph.print(&Config::default()), "<a/>");
+    }
+
+    #[test]
+    fn test_print_prolog_only() {
+        let verbose = Config {
+            verbose: true,
+            ..Config::default()
+        };
+        let graph = graph_from("<?xml version=\"1.0\"?>");
+        assert_eq!(graph.print(&Config::default()), "<?xml version=\"1.0\"?>");
+        assert_eq!(
+            graph.print(&verbose),
+            "<?xml version=\"1.0\"?>\n<!-- no root element found -->"
+        );
+
+        let mut graph = graph_from("<?xml version=\"1.0\"?>\n<!-- only a comment -->\n");
+        graph.minimize(&Config::default());
+        assert_eq!(graph.print(&Config::default()), "<?xml version=\"1.0\"?>");
+        assert_eq!(
+            graph.print(&verbose),
+            "<?xml version=\"1.0\"?>\n<!-- no root element found -->"
+        );
+        assert!(graph.warnings().is_empty());
+    }
+
+    #[test]
+    fn test_print_sort_attributes() {
+        let graph = graph_from("<x b=\"2\" a=\"1\" c=\"3\"><y z=\"\" y=\"\"/></x>");
+        let config = Config {
+            sort_attributes: true,
+            ..Config::default()
+        };
+
+        assert_eq!(
+            graph.print(&config),
+            "<x a=\"1\" b=\"2\" c=\"3\"><y y=\"\" z=\"\"/></x>"
+        );
+        assert_eq!(
+            graph.print(&Config::default()),
+            "<x b=\"2\" a=\"1\" c=\"3\"><y z=\"\" y=\"\"/></x>"
+        );
+    }
+
+    #[test]
+    fn test_print_self_closing() {
+        let graph = graph_from("<a><x/><y></y><z> </z></a>");
+        assert_eq!(
+            graph.print(&Config::default()),
+            "<a><x/><y></y><z> </z></a>"
+        );
+        assert!(graph.find_by_name("x")[0].self_closing);
+        assert!(!graph.find_by_name("y")[0].self_closing);
+        assert_eq!(graph.find_by_name("y")[0].to_string(), "<y>");
+
+        let no_text = Config {
+            no_text: true,
+            ..Config::default()
+        };
+        assert_eq!(graph.print(&no_text), "<a><x/><y/><z/></a>");
+    }
+
+    #[test]
+    fn test_print_color() {
+        let graph = graph_from("<a x=\"1\">Text <b/></a>");
+        let config = Config {
+            color: true,
+            ..Config::default()
+        };
+
+        assert_eq!(
+            graph.print(&config),
+            "<\x1b[1m\x1b[34ma\x1b[0m \x1b[36mx\x1b[0m=\"1\">\x1b[32mText\x1b[0m <\x1b[1m\x1b[34mb\x1b[0m/></\x1b[1m\x1b[34ma\x1b[0m>"
+        );
+        assert_eq!(graph.print(&Config::default()), "<a x=\"1\">Text <b/></a>");
+    }
+
+    #[test]
+    fn test_print_fragment() {
+        let mut graph = graph_from("<a/><b x=\"1\"><c/></b>text<a/>\n<d/>");
+        assert_eq!(
+            graph.print(&Config::default()),
+            "<a/>\n<b x=\"1\"><c/></b>\n<a/>\n<d/>"
+        );
+
+        graph.minimize(&Config::default());
+        assert_eq!(
+            graph.print(&Config::default()),
+            "<a/>\n<b x=\"1\"><c/></b>\n<d/>"
+        );
+    }
+
+    #[test]
+    fn test_print_depth() {
+        let graph = graph_from("<a><b><c><d>Text</d></c></b><e>Text</e></a>");
+        let print_with_depth = |depth| {
+            graph.print(&Config {
+                depth,
+                ..Config::default()
+            })
+        };
+
+        assert_eq!(print_with_depth(Some(0)), "<a .../>");
+        assert_eq!(print_with_depth(Some(1)), "<a><b .../><e>Text</e></a>");
+        assert_eq!(
+            print_with_depth(Some(2)),
+            "<a><b><c .../></b><e>Text</e></a>"
+        );
+        assert_eq!(
+            print_with_depth(Some(3)),
+            "<a><b><c><d>Text</d></c></b><e>Text</e></a>"
+        );
+        assert_eq!(print_with_depth(None), print_with_depth(Some(3)));
+    }
+
+    #[test]
+    fn test_print_max_children() {
+        let graph = graph_from("<rows>\n<row/>\n<row/>\n<row/>\n<row/>\n</rows>");
+        let print_with_max_children = |max_children, indent| {
+            graph.print(&Config {
+                max_children,
+                indent,
+                ..Config::default()
+            })
+        };
+
+        assert_eq!(
+            print_with_max_children(Some(2), 0),
+            "<rows>\n<row/>\n<row/>\n<!-- 2 more children omitted -->\n</rows>"
+        );
+        assert_eq!(
+            print_with_max_children(Some(3), 2),
+            "<rows>\n  <row/>\n  <row/>\n  <row/>\n  <!-- 1 more child omitted -->\n</rows>"
+        );
+        assert_eq!(
+            print_with_max_children(Some(4), 0),
+            print_with_max_children(None, 0)
+        );
+
+        let graph = graph_from(
+            "<catalog>\n  <book>\n    <title/>\n    <price/>\n  </book>\n  <shelf/>\n</catalog>",
+        );
+        assert_eq!(
+            graph.print(&Config {
+                max_children: Some(1),
+                ..Config::default()
+            }),
+            "<catalog>\n  <book>\n    <title/>\n    <!-- 1 more child omitted -->\n  </book>\n  <!-- 1 more child omitted -->\n</catalog>"
+        );
+    }
+
+    #[test]
+    fn test_minimize_keeps_node_with_most_children() {
+        let mut graph = graph_from("<list><item/><item><name/></item><other/></list>");
+        graph.minimize(&Config::default());
+
+        assert_eq!(
+            graph.print(&Config::default()),
+            "<list><item><name/></item><other/></list>"
+        );
+    }
+
+    #[test]
+    fn test_minimize_is_stable() {
+        let xml = "<r><z/><b id=\"1\"/><a/><z/><m/><b/><a/><b id=\"2\"/><c><y/><x/><y/></c></r>";
+        let overview = |strategy| {
+            let config = Config {
+                minimize_strategy: strategy,
+                ..Config::default()
+            };
+            let mut graph = graph_from(xml);
+            graph.minimize(&config);
+            graph.print(&config)
+        };
+
+        // The survivors keep the order in which their group first appears.
+        assert_eq!(
+            overview(MinimizeStrategy::MostChildren),
+            "<r><z/><b id=\"1\"/><a/><m/><b/><c><y/><x/></c></r>"
+        );
+        for strategy in [
+            MinimizeStrategy::MostChildren,
+            MinimizeStrategy::First,
+            MinimizeStrategy::Merge,
+        ] {
+            assert_eq!(overview(strategy), overview(strategy));
+        }
+    }
+
+    #[test]
+    fn test_minimize_drops_whitespace_of_collapsed() {
+        let mut graph =
+            graph_from("<list>\n  <item/>\n  <item/>\n\t<item/> text <item/>\n  <other/>\n</list>");
+        graph.minimize(&Config::default());
+
+        assert_eq!(
+            graph.print(&Config::default()),
+            "<list>\n  <item/> text \n  <other/>\n</list>"
+        );
+    }
+
+    #[test]
+    fn test_minimize_keeps_different_keys() {
+        let mut graph = graph_from("<list><item id=\"1\"/><item/><item id=\"2\"/></list>");
+        graph.minimize(&Config::default());
+
+        assert_eq!(
+            graph.print(&Config::default()),
+            "<list><item id=\"1\"/><item/></list>"
+        );
+    }
+
+    #[test]
+    fn test_minimize_collapse_threshold() {
+        let xml = "<list><item/><item/><other/><other/><other/></list>";
+
+        let mut graph = graph_from(xml);
+        graph.minimize(&Config {
+            collapse_threshold: 3,
+            ..Config::default()
+        });
+        assert_eq!(
+            graph.print(&Config::default()),
+            "<list><item/><item/><other/></list>"
+        );
+
+        let mut graph = graph_from(xml);
+        graph.minimize(&Config {
+            collapse_threshold: 2,
+            ..Config::default()
+        });
+        assert_eq!(
+            graph.print(&Config::default()),
+            "<list><item/><other/></list>"
+        );
+    }
+
+    #[test]
+    fn test_verbose_collapsed_occurrences() {
+        let xml = "<list><item/><item/><item/><other/></list>";
+        let mut graph = graph_from(xml);
+        graph.minimize(&Config::default());
+
+        let verbose = Config {
+            verbose: true,
+            ..Config::default()
+        };
+        assert_eq!(
+            graph.print(&verbose),
+            "<list><item/> <!-- 2 occurrences collapsed --> <!-- always empty --><other/> <!-- always empty --></list>"
+        );
+        assert_eq!(
+            graph.print(&Config::default()),
+            "<list><item/><other/></list>"
+        );
+    }
+
+    #[test]
+    fn test_namespaced_names() {
+        let graph = graph_from("<soap:Envelope><soap:Body/><Header/></soap:Envelope>");
+
+        let envelope = graph.get_node(1);
+        assert_eq!(envelope.prefix.as_deref(), Some("soap"));
+        assert_eq!(envelope.local_name, "Envelope");
+        let header = graph.get_node(3);
+        assert_eq!(header.prefix, None);
+        assert_eq!(header.local_name, "Header");
+        assert_eq!(
+            graph.print(&Config::default()),
+            "<soap:Envelope><soap:Body/><Header/></soap:Envelope>"
+        );
+    }
+
+    #[test]
+    fn test_print_decodes_entities() {
+        let graph = graph_from("<a>Fish &amp; chips &#169; & more</a>");
+
+        assert_eq!(
+            graph.print(&Config::default()),
+            "<a>Fish &amp; chips © &amp; more</a>"
+        );
+        let config = Config {
+            raw_text: true,
+            ..Config::default()
+        };
+        assert_eq!(
+            graph.print(&config),
+            "<a>Fish &amp; chips &#169; & more</a>"
+        );
+    }
+
+    #[test]
+    fn test_print_element() {
+        let mut graph = graph_from(
+            "<shop><books><book id=\"1\"><title/></book><book id=\"2\"><title/></book></books>\
+            <sale><book id=\"3\"><title/></book></sale><book id=\"4\"><book/></book></shop>",
+        );
+        graph.minimize(&Config::default());
+
+        assert_eq!(
+            graph.print_element("book", &Config::default()),
+            Some(String::from(
+                "<book id=\"1\"><title/></book>\n<book id=\"4\"><book/> <!-- recursive --></book>"
+            ))
+        );
+        assert_eq!(graph.print_element("magazine", &Config::default()), None);
+        assert_eq!(
+            graph.print_element("shop/sale/book", &Config::default()),
+            Some(String::from("<book id=\"3\"><title/></book>"))
+        );
+    }
+
+    #[test]
+    fn test_find_by_name_and_path() {
+        let graph = graph_from(
+            "<catalog><book><author>A</author></book><book><author>B</author><book/></book></catalog>",
+        );
+        let ids =
+            |nodes: Vec<&Node>| -> Vec<usize> { nodes.into_iter().map(|node| node.id).collect() };
+
+        assert_eq!(ids(graph.find_by_name("book")), vec![2, 4, 6]);
+        assert_eq!(ids(graph.find_by_name("author")), vec![3, 5]);
+        assert_eq!(ids(graph.find_by_path("catalog/book")), vec![2, 4]);
+        assert_eq!(ids(graph.find_by_path("/catalog/book/author")), vec![3, 5]);
+        assert_eq!(ids(graph.find_by_path("catalog/book/book")), vec![6]);
+        assert!(graph.find_by_path("book").is_empty());
+        assert!(graph.find_by_path("").is_empty());
+    }
+
+    #[test]
+    fn test_keep_only() {
+        let mut graph = graph_from(
+            "<catalog>\n  <book>\n    <title>A</title>\n    <price>1</price>\n  </book>\n  <magazine><price>2</price></magazine>\n</catalog>",
+        );
+        graph.keep_only(&[String::from("catalog/book/price")]);
+
+        assert_eq!(
+            graph.print(&Config::default()),
+            "<catalog>\n  <book>\n    <price>1</price>\n  </book>\n</catalog>"
+        );
+        assert!(graph.warnings().is_empty());
+
+        let mut graph = graph_from("<a><b/></a>");
+        graph.keep_only(&[String::from("a/c")]);
+        assert_eq!(graph.print(&Config::default()), "");
+        assert_eq!(graph.warnings(), ["No element is at the path a/c"]);
+    }
+
+    #[test]
+    fn test_print_indent() {
+        let graph = graph_from(
+            "<a>  <b x=\"1\">\n\n\t<c> Text </c><!-- note --><d/></b>Tail<e/>\n</a>\n<f/>",
+        );
+        let config = Config {
+            indent: 2,
+            verbose: true,
+            ..Config::default()
+        };
+
+        assert_eq!(
+            graph.print(&config),
+            "<a>\n  <b x=\"1\">\n    <c>Text</c>\n    <!-- note -->\n    <d/> <!-- always empty -->\n  </b>\n  Tail\n  <e/> <!-- always empty -->\n</a>\n<f/> <!-- always empty -->"
+        );
+    }
+
+    #[test]
+    fn test_print_escapes_values() {
+        let graph = graph_from("<a title=\"a &amp; b &lt; c\" quote='say \"hi\"'>1 &lt; 2</a>");
+
+        assert_eq!(graph.get_node(1).values, vec!["a & b < c", "say \"hi\""]);
+        assert_eq!(
+            graph.print(&Config::default()),
+            "<a title=\"a &amp; b &lt; c\" quote=\"say &quot;hi&quot;\">1 &lt; 2</a>"
+        );
+    }
+
+    #[test]
+    fn test_counts() {
+        let mut graph = graph_from(
+            "<list><group><item/><item/></group><group><item/></group><group><item/><item/><item/></group></list>",
+        );
+        let config = Config {
+            counts: true,
+            ..Config::default()
+        };
+        graph.minimize(&config);
+
+        assert_eq!(
+            graph.print(&config),
+            "<list><group><item/> (×6)</group> (×3)</list>"
+        );
+    }
+
+    #[test]
+    fn test_cardinality() {
+        let mut graph = graph_from(
+            "<list>\
+                <group><id/><item/><item/><tag/><tag/><note/></group>\
+                <group><id/><item/></group>\
+                <group><id/><item/></group>\
+            </list>",
+        );
+        let config = Config {
+            cardinality: true,
+            ..Config::default()
+        };
+        graph.minimize(&config);
+
+        assert_eq!(
+            graph.print(&config),
+            "<list><group><id/><item/> <!-- cardinality: + --><tag/> <!-- cardinality: * --><note/> <!-- cardinality: ? --></group> <!-- cardinality: + --></list>"
+        );
+    }
+
+    #[test]
+    fn test_verbose_nested_populated() {
+        let mut graph =
+            graph_from("<list><item><flag/></item><item><flag>yes</flag></item></list>");
+        let config = Config {
+            verbose: true,
+            ..Config::default()
+        };
+        graph.minimize(&config);
+
+        assert_eq!(
+            graph.print(&config),
+            "<list><item><flag/></item> <!-- 1 occurrence collapsed --></list>"
+        );
+    }
+
+    #[test]
+    fn test_cardinality_sometimes_empty() {
+        let mut graph = graph_from(
+            "<list>\
+                <item><note/><price>1</price></item>\
+                <item><note>Sold out</note><price>2</price></item>\
+                <item><note> </note><price>3</price></item>\
+            </list>",
+        );
+        let config = Config {
+            cardinality: true,
+            ..Config::default()
+        };
+        graph.minimize(&config);
+
+        assert_eq!(
+            graph.print(&config),
+            "<list><item><note/> <!-- sometimes empty --><price>1</price></item> <!-- cardinality: + --></list>"
+        );
+    }
+
+    #[test]
+    fn test_minimize_first() {
+        let mut graph = graph_from("<list><item id=\"1\"/><item id=\"2\"><name/></item></list>");
+        let config = Config {
+            minimize_strategy: MinimizeStrategy::First,
+            ..Config::default()
+        };
+        graph.minimize(&config);
+
+        assert_eq!(graph.print(&config), "<list><item id=\"1\"/></list>");
+    }
+
+    #[test]
+    fn test_minimize_merge() {
+        let mut graph = graph_from(
+            "<list>\
+                <item id=\"1\"><name/><tags><tag/></tags></item>\
+                <item id=\"2\" lang=\"en\"><price/><tags><tag/><label/></tags></item>\
+            </list>",
+        );
+        let config = Config {
+            minimize_strategy: MinimizeStrategy::Merge,
+            cardinality: true,
+            ..Config::default()
+        };
+        graph.minimize(&config);
+
+        assert_eq!(
+            graph.print(&config),
+            "<list><item id=\"1\" lang?=\"en\"><name/> <!-- cardinality: ? --><tags><tag/><label/> <!-- cardinality: ? --></tags><price/> <!-- cardinality: ? --></item> <!-- cardinality: + --></list>"
+        );
+    }
+
+    #[test]
+    fn test_minimize_merge_layout() {
+        let mut graph = graph_from(
+            "<catalog>
+  <book id=\"1\">
+    <title/>
+    <price/>
+  </book>
+  <book id=\"2\">
+    <title/>
+    <price/>
+    <note/>
+  </book>
+</catalog>",
+        );
+        let config = Config {
+            minimize_strategy: MinimizeStrategy::Merge,
+            ..Config::default()
+        };
+        graph.minimize(&config);
+
+        assert_eq!(
+            graph.print(&config),
+            "<catalog>
+  <book id=\"1\">
+    <title/>
+    <price/>
+    <note/>
+  </book>
+</catalog>"
+        );
+    }
+
+    #[test]
+    fn test_minimize_merge_optional_keys() {
+        let mut graph = graph_from(
+            "<list><item id=\"1\"/><item id=\"2\" label=\"b\"/><item label=\"c\" id=\"3\"/></list>",
+        );
+        let config = Config {
+            minimize_strategy: MinimizeStrategy::Merge,
+            ..Config::default()
+        };
+        graph.minimize(&config);
+
+        assert_eq!(
+            graph.print(&config),
+            "<list><item id=\"1\" label?=\"b\"/></list>"
+        );
+        assert_eq!(graph.get_node(2).key_occurrences, vec![3, 2]);
+        assert_eq!(graph.get_node(2).key_presence("id"), Some(1.0));
+        assert_eq!(graph.get_node(2).key_presence("name"), None);
+        assert_eq!(
+            graph.print(&Config {
+                plain_keys: true,
+                verbose: true,
+                ..config
+            }),
+            "<list><item id=\"1\" label=\"b\"/> <!-- 2 occurrences collapsed --> <!-- label in 67% of instances --></list>"
+        );
+    }
+
+    #[test]
+    fn test_show_mixed() {
+        let graph = graph_from("<doc><p>Some <b>bold</b> text</p><list>\n  <item/>\n</list></doc>");
+        let config = Config {
+            show_mixed: true,
+            ..Config::default()
+        };
+
+        assert_eq!(
+            graph.print(&config),
+            "<doc><p>Some <b>bold</b> text</p> <!-- mixed content --><list>\n  <item/>\n</list></doc>"
+        );
+    }
+
+    #[test]
+    fn test_verbose_comments() {
+        let graph = graph_from("<a><!-- first --><b/><!--second--></a>");
+        let verbose = Config {
+            verbose: true,
+            ..Config::default()
+        };
+
+        assert_eq!(graph.print(&Config::default()), "<a><b/></a>");
+        assert_eq!(
+            graph.print(&verbose),
+            "<a><!-- first --><b/> <!-- always empty --><!--second--></a>"
+        );
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("abcdef", 3), "abc…");
+        assert_eq!(truncate("abc", 3), "abc");
+        assert_eq!(truncate("日本語のテキスト", 2), "日本…");
+        assert_eq!(truncate("abcdef", 0), "abcdef");
+    }
+
+    #[test]
+    fn test_print_max_value_len() {
+        let graph = graph_from(
+            "<img src=\"data:image/png;base64,iVBORw0KGgo\" alt=\"ok\">Größere Beschreibung</img>",
+        );
+        let config = Config {
+            max_value_len: 5,
+            ..Config::default()
+        };
+
+        assert_eq!(
+            graph.print(&config),
+            "<img src=\"data:…\" alt=\"ok\">Größe…</img>"
+        );
+    }
+
+    #[test]
+    fn test_print_max_value_len_cdata() {
+        let script = "x".repeat(200);
+        let graph = graph_from(&format!("<script><![CDATA[{}]]></script>", script));
+        let config = Config {
+            max_value_len: 40,
+            ..Config::default()
+        };
+
+        assert_eq!(
+            graph.print(&config),
+            format!("<script><![CDATA[{}…]]></script>", "x".repeat(40))
+        );
+    }
+
+    #[test]
+    fn test_print_code_elements() {
+        let graph = graph_from(
+            "<svg><style><![CDATA[rect { fill: red }]]></style><script>\n  draw();\n</script><text>Hi</text></svg>",
+        );
+        let config = Config {
+            code_elements: vec![String::from("script"), String::from("style")],
+            ..Config::default()
+        };
+
+        assert_eq!(
+            graph.print(&config),
+            "<svg><style><![CDATA[…]]></style><script>…</script><text>Hi</text></svg>"
+        );
+    }
+
+    #[test]
+    fn test_collapse_whitespace() {
+        assert_eq!(collapse_whitespace("a  \t b"), "a b");
+        assert_eq!(collapse_whitespace("a \n\n  b "), "a\nb ");
+    }
+
+    #[test]
+    fn test_normalize_whitespace() {
+        let graph = graph_from("<a>\n    <b>Some   text</b>  \n\n    <c/>   </a>");
+        let config = Config {
+            normalize_whitespace: true,
+            ..Config::default()
+        };
+
+        assert_eq!(
+            graph.print(&Config::default()),
+            "<a>\n    <b>Some   text</b>  \n    <c/>   </a>"
+        );
+        assert_eq!(graph.print(&config), "<a>\n<b>Some text</b>\n<c/> </a>");
+    }
+
+    #[test]
+    fn test_always_empty() {
+        let mut graph =
+            graph_from("<list><marker/><marker/><item> </item><item>Text</item></list>");
+        graph.minimize(&Config::default());
+
+        let config = Config {
+            verbose: true,
+            ..Config::default()
+        };
+        assert_eq!(
+            graph.print(&config),
+            "<list><marker/> <!-- 1 occurrence collapsed --> <!-- always empty --><item> </item> <!-- 1 occurrence collapsed --></list>"
+        );
+    }
+
+    #[test]
+    fn test_minimize_keeps_description() {
+        let mut graph = graph_from("<list><item/><!-- An item --><item/></list>");
+        graph.minimize(&Config::default());
+
+        let item = graph.nodes.iter().find(|node| node.name == "item").unwrap();
+        assert_eq!(item.description, Some(String::from("An item")));
+    }
+
+    #[test]
+    fn test_xml_declaration() {
+        let graph = graph_from("<?xml version=\"1.0\" standalone=\"yes\"?>\n<root/>");
+
+        assert_eq!(
+            graph.xml_declaration(),
+            Some(XmlDeclaration {
+                version: String::from("1.0"),
+                encoding: None,
+                standalone: Some(true),
+            })
+        );
+        assert_eq!(
+            graph.print(&Config::default()),
+            "<?xml version=\"1.0\" standalone=\"yes\"?>\n<root/>"
+        );
+    }
+
+    #[test]
+    fn test_no_xml_declaration() {
+        assert_eq!(graph_from("<root/>").xml_declaration(), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_msgpack_is_tagged_with_format_version() {
+        #[derive(serde::Deserialize)]
+        struct Header {
+            format_version: u32,
+        }
+
+        let bytes = graph_from("<root/>").to_msgpack().unwrap();
+        let header: Header = rmp_serde::from_slice(&bytes).unwrap();
+
+        assert_eq!(header.format_version, MSGPACK_FORMAT_VERSION);
+    }
+}
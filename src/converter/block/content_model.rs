@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+
+use super::{Graph, cardinality};
+
+/// The children of a single instance of an element.
+struct Instance {
+    names: Vec<String>,
+    has_text: bool,
+}
+
+impl Instance {
+    fn count(&self, name: &str) -> usize {
+        self.names.iter().filter(|n| *n == name).count()
+    }
+}
+
+impl Graph {
+    /// Computes a DTD-style content model like `(title, author+, (summary | abstract)?, section*)`
+    /// for the element of the given node.
+    ///
+    /// Like in a DTD, the model is derived from every instance with the same name, including the instances
+    /// that minimize collapsed. Only the children that are still in the tree count, so every name in the model
+    /// is an element of the tree. Children that never appear together in one instance but take the same
+    /// position are combined into a choice.
+    pub fn content_model(&self, node_id: usize) -> String {
+        let name = &self.get_node(node_id).name;
+
+        // The instances the tree describes, the reachable nodes and the ones collapsed into them, in document order.
+        let mut described: Vec<usize> = self.iter().map(|node| node.id).collect();
+        let mut index = 0;
+        while index < described.len() {
+            described.extend_from_slice(&self.nodes[described[index]].absorbed);
+            index += 1;
+        }
+        described.sort_unstable();
+        described.dedup();
+
+        let mut children = HashMap::<usize, Vec<String>>::new();
+        for &id in &described {
+            if let Some(origin) = self.nodes[id].origin {
+                children
+                    .entry(origin)
+                    .or_default()
+                    .push(self.nodes[id].name.clone());
+            }
+        }
+        let instances: Vec<Instance> = described
+            .iter()
+            .filter(|&&id| self.nodes[id].name == *name)
+            .map(|&id| Instance {
+                names: children.remove(&id).unwrap_or_default(),
+                has_text: self.nodes[id].has_text(),
+            })
+            .collect();
+
+        // The distinct child names, in the order they appear across the instances.
+        let mut names = Vec::<String>::new();
+        let mut first_seen = Vec::<String>::new();
+        for instance in &instances {
+            let mut position = 0;
+            for name in &instance.names {
+                match names.iter().position(|n| n == name) {
+                    Some(index) => position = position.max(index + 1),
+                    None => {
+                        names.insert(position, name.clone());
+                        first_seen.push(name.clone());
+                        position += 1;
+                    }
+                }
+            }
+        }
+
+        let has_text = instances.iter().any(|instance| instance.has_text);
+        if names.is_empty() {
+            return String::from(if has_text { "(#PCDATA)" } else { "EMPTY" });
+        }
+        if has_text {
+            return format!("(#PCDATA | {})*", names.join(" | "));
+        }
+
+        let co_occur = |a: &str, b: &str| {
+            instances
+                .iter()
+                .any(|instance| instance.count(a) > 0 && instance.count(b) > 0)
+        };
+
+        // Neighbouring names that never share an instance are alternatives for the same slot.
+        let mut groups = Vec::<Vec<String>>::new();
+        for name in names {
+            match groups.last_mut() {
+                Some(group) if group.iter().all(|member| !co_occur(member, &name)) => {
+                    group.push(name)
+                }
+                _ => groups.push(vec![name]),
+            }
+        }
+        for group in &mut groups {
+            group.sort_by_key(|name| first_seen.iter().position(|n| n == name));
+        }
+
+        let items: Vec<String> = groups
+            .iter()
+            .map(|group| {
+                let counts = instances
+                    .iter()
+                    .map(|instance| group.iter().map(|name| instance.count(name)).sum::<usize>());
+                let min = counts.clone().min().unwrap_or(0);
+                let max = counts.max().unwrap_or(0);
+
+                if let [name] = group.as_slice() {
+                    return format!("{}{}", name, cardinality(min, max));
+                }
+
+                let members: Vec<String> = group
+                    .iter()
+                    .map(|name| {
+                        let max = instances
+                            .iter()
+                            .map(|instance| instance.count(name))
+                            .max()
+                            .unwrap_or(0);
+                        format!("{}{}", name, cardinality(1, max))
+                    })
+                    .collect();
+                format!("({}){}", members.join(" | "), cardinality(min, max))
+            })
+            .collect();
+
+        // A choice on its own is already a valid content model, it doesn't need to be wrapped again.
+        match (groups.as_slice(), items.as_slice()) {
+            ([group], [item]) if group.len() > 1 => item.clone(),
+            _ => format!("({})", items.join(", ")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::converter::config::{Config, MinimizeStrategy};
+    use crate::converter::parser::{lex_graph, lex_tokens};
+
+    fn content_model_of(xml: &str, name: &str) -> String {
+        let graph = lex_graph(lex_tokens(xml).unwrap(), &Config::default()).unwrap();
+        let node = graph.nodes.iter().find(|node| node.name == name).unwrap();
+
+        graph.content_model(node.id)
+    }
+
+    #[test]
+    fn test_content_model_sequence() {
+        let xml = "<books>\
+            <book><title/><author/><summary/><section/><section/></book>\
+            <book><title/><author/><author/><abstract/></book>\
+            <book><title/><author/></book>\
+        </books>";
+
+        assert_eq!(
+            content_model_of(xml, "book"),
+            "(title, author+, (summary | abstract)?, section*)"
+        );
+    }
+
+    #[test]
+    fn test_content_model_minimized() {
+        let xml =
+            "<books><book><title/><author/><author/></book><book><title/><author/></book></books>";
+        let mut graph = lex_graph(lex_tokens(xml).unwrap(), &Config::default()).unwrap();
+        graph.minimize(&Config::default());
+
+        assert_eq!(graph.content_model(2), "(title, author+)");
+        assert_eq!(graph.content_model(1), "(book+)");
+    }
+
+    #[test]
+    fn test_content_model_text() {
+        assert_eq!(content_model_of("<a><b/></a>", "b"), "EMPTY");
+        assert_eq!(content_model_of("<a><b>Text</b></a>", "b"), "(#PCDATA)");
+        assert_eq!(
+            content_model_of("<a>Text <b/> and <c/></a>", "a"),
+            "(#PCDATA | b | c)*"
+        );
+    }
+
+    #[test]
+    fn test_content_model_collapsed_instances() {
+        let xml = "<r><x><a/></x><x><b/></x></r>";
+        for (strategy, model) in [
+            (MinimizeStrategy::Merge, "(a | b)"),
+            (MinimizeStrategy::MostChildren, "(a?)"),
+        ] {
+            let config = Config {
+                minimize_strategy: strategy,
+                ..Config::default()
+            };
+            let mut graph = lex_graph(lex_tokens(xml).unwrap(), &config).unwrap();
+            graph.minimize(&config);
+
+            assert_eq!(graph.content_model(2), model);
+        }
+        assert_eq!(content_model_of(xml, "x"), "(a | b)");
+    }
+}
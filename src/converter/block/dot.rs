@@ -0,0 +1,76 @@
+use super::Graph;
+
+/// Escapes a string for use inside a double-quoted DOT identifier.
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+impl Graph {
+    /// Renders the tree as a Graphviz digraph, without the synthetic root.
+    /// Every element becomes a DOT node identified by its id, labelled with its name and number of attributes.
+    pub fn to_dot(&self) -> String {
+        let mut res = String::from("digraph {\n");
+
+        let mut stack: Vec<usize> = self.child_ids(0).into_iter().rev().collect();
+        let mut edges = Vec::new();
+        while let Some(id) = stack.pop() {
+            let node = self.get_node(id);
+            let attributes = if node.keys.len() == 1 {
+                "attribute"
+            } else {
+                "attributes"
+            };
+            res.push_str(&format!(
+                "    {} [label=\"{} ({} {})\"];\n",
+                node.id,
+                escape(&node.name),
+                node.keys.len(),
+                attributes
+            ));
+
+            let children = self.child_ids(id);
+            edges.extend(children.iter().map(|child_id| (id, *child_id)));
+            stack.extend(children.into_iter().rev());
+        }
+
+        for (parent, child) in edges {
+            res.push_str(&format!("    {} -> {};\n", parent, child));
+        }
+
+        res.push('}');
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::escape;
+    use crate::converter::config::Config;
+    use crate::converter::parser::{lex_graph, lex_tokens};
+
+    #[test]
+    fn test_to_dot() {
+        let tokens = lex_tokens(
+            "<catalog><book id=\"1\" lang=\"en\"><title>Text</title></book><book id=\"2\" lang=\"nl\"/></catalog>",
+        )
+        .unwrap();
+        let mut graph = lex_graph(tokens, &Config::default()).unwrap();
+        graph.minimize(&Config::default());
+
+        assert_eq!(
+            graph.to_dot(),
+            "digraph {\n\
+            \x20   1 [label=\"catalog (0 attributes)\"];\n\
+            \x20   2 [label=\"book (2 attributes)\"];\n\
+            \x20   3 [label=\"title (0 attributes)\"];\n\
+            \x20   1 -> 2;\n\
+            \x20   2 -> 3;\n\
+            }"
+        );
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape("a\"b\\c"), "a\\\"b\\\\c");
+    }
+}
@@ -0,0 +1,85 @@
+use super::Graph;
+
+impl Graph {
+    /// Generates a DTD from the tree, with an `<!ELEMENT>` declaration for every distinct element name in the
+    /// order they appear, see [`Graph::content_model`]. Every attribute an element has in any of its instances
+    /// is declared as optional `CDATA`.
+    pub fn to_dtd(&self) -> String {
+        let mut declarations = Vec::<String>::new();
+        let mut names = Vec::<&str>::new();
+        for node in self.iter() {
+            if names.contains(&node.name.as_str()) {
+                continue;
+            }
+            names.push(&node.name);
+
+            let mut declaration =
+                format!("<!ELEMENT {} {}>", node.name, self.content_model(node.id));
+            let mut keys = Vec::<&str>::new();
+            for instance in self.iter().filter(|instance| instance.name == node.name) {
+                for key in &instance.keys {
+                    if !keys.contains(&key.as_str()) {
+                        keys.push(key);
+                    }
+                }
+            }
+            if !keys.is_empty() {
+                let attributes: Vec<String> = keys
+                    .iter()
+                    .map(|key| format!("\n  {} CDATA #IMPLIED", key))
+                    .collect();
+                declaration.push_str(&format!(
+                    "\n<!ATTLIST {}{}>",
+                    node.name,
+                    attributes.concat()
+                ));
+            }
+            declarations.push(declaration);
+        }
+
+        declarations.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::converter::config::Config;
+    use crate::converter::parser::{lex_graph, lex_tokens};
+
+    #[test]
+    fn test_to_dtd() {
+        let tokens = lex_tokens(
+            "<catalog><book id=\"1\"><title>A</title><author/><author/></book><book id=\"2\" lang=\"en\"><title>B</title><author/><cover/></book><book id=\"3\"><title>C</title></book></catalog>",
+        )
+        .unwrap();
+        let mut graph = lex_graph(tokens, &Config::default()).unwrap();
+        graph.minimize(&Config::default());
+
+        assert_eq!(
+            graph.to_dtd(),
+            "<!ELEMENT catalog (book+)>\n\
+            <!ELEMENT book (title, author*, cover?)>\n\
+            <!ATTLIST book\n  id CDATA #IMPLIED\n  lang CDATA #IMPLIED>\n\
+            <!ELEMENT title (#PCDATA)>\n\
+            <!ELEMENT author EMPTY>\n\
+            <!ELEMENT cover EMPTY>"
+        );
+    }
+
+    #[test]
+    fn test_to_dtd_collapsed_instances() {
+        let tokens = lex_tokens("<r><x><a/></x><x><b/></x></r>").unwrap();
+        let mut graph = lex_graph(tokens, &Config::default()).unwrap();
+        graph.minimize(&Config::default());
+
+        // The `<b>` of the collapsed `<x>` isn't in the tree, so it isn't referred to either.
+        assert_eq!(
+            graph.to_dtd(),
+            "<!ELEMENT r (x+)>\n<!ELEMENT x (a?)>\n<!ELEMENT a EMPTY>"
+        );
+
+        let tokens = lex_tokens("<r><x><a/></x><y><b/></y><x><b/></x></r>").unwrap();
+        let graph = lex_graph(tokens, &Config::default()).unwrap();
+        assert!(graph.to_dtd().contains("<!ELEMENT x (a | b)>"));
+    }
+}
@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+
+use super::{Graph, Node, ValueType};
+
+/// An attribute of an element, combined over every node with the element's name.
+pub(super) struct AttributeField {
+    pub name: String,
+    pub value_type: ValueType,
+    /// Whether some of the instances don't have the attribute.
+    pub optional: bool,
+}
+
+/// A child of an element, combined over every node with the element's name.
+pub(super) struct ChildField {
+    pub name: String,
+    /// The smallest and largest number of times the child occurs in one instance of the element.
+    pub min_occurs: usize,
+    pub max_occurs: usize,
+    /// Whether the child contains an element with its own name, so a type for it has to be boxed.
+    pub recursive: bool,
+}
+
+/// The attributes, children and text of every instance of an element, as the fields of a generated type.
+pub(super) struct ElementFields {
+    pub name: String,
+    pub attributes: Vec<AttributeField>,
+    pub children: Vec<ChildField>,
+    pub has_text: bool,
+    /// The first comment that documents one of the instances.
+    pub description: Option<String>,
+}
+
+impl Graph {
+    /// The fields of every distinct element name, in the order the names appear. The fields of an element are
+    /// combined from every node with its name, so an attribute or child that only a later instance has isn't
+    /// missed, and children with the same name that minimize kept apart count as repetitions of one child.
+    pub(super) fn element_fields(&self) -> Vec<ElementFields> {
+        let mut groups = Vec::<Vec<&Node>>::new();
+        let mut group_of = HashMap::<&str, usize>::new();
+        for node in self.iter() {
+            match group_of.get(node.name.as_str()) {
+                Some(&index) => groups[index].push(node),
+                None => {
+                    group_of.insert(&node.name, groups.len());
+                    groups.push(vec![node]);
+                }
+            }
+        }
+
+        groups
+            .into_iter()
+            .map(|nodes| self.fields_of(&nodes))
+            .collect()
+    }
+
+    /// Combines the fields of the nodes, which all have the same name.
+    fn fields_of(&self, nodes: &[&Node]) -> ElementFields {
+        let mut attributes = Vec::<AttributeField>::new();
+        for node in nodes {
+            for (key, value_type) in node.keys.iter().zip(&node.types) {
+                match attributes
+                    .iter_mut()
+                    .find(|attribute| attribute.name == *key)
+                {
+                    Some(attribute) => {
+                        attribute.value_type = attribute.value_type.widen(*value_type)
+                    }
+                    None => attributes.push(AttributeField {
+                        name: key.clone(),
+                        value_type: *value_type,
+                        optional: false,
+                    }),
+                }
+            }
+        }
+        for attribute in &mut attributes {
+            attribute.optional = nodes.iter().any(|node| {
+                node.key_presence(&attribute.name)
+                    .is_none_or(|presence| presence < 1.0)
+            });
+        }
+
+        // Siblings with the same name that weren't collapsed into one node add up within their parent.
+        let counts: Vec<Vec<ChildField>> = nodes
+            .iter()
+            .map(|node| {
+                let mut counts = Vec::<ChildField>::new();
+                for child in self.children_of(node.id) {
+                    let recursive = child.is_recursive(self);
+                    match counts.iter_mut().find(|count| count.name == child.name) {
+                        Some(count) => {
+                            count.min_occurs += child.min_occurs;
+                            count.max_occurs += child.max_occurs;
+                            count.recursive |= recursive;
+                        }
+                        None => counts.push(ChildField {
+                            name: child.name.clone(),
+                            min_occurs: child.min_occurs,
+                            max_occurs: child.max_occurs,
+                            recursive,
+                        }),
+                    }
+                }
+                counts
+            })
+            .collect();
+        let mut children = Vec::<ChildField>::new();
+        for count in counts.iter().flatten() {
+            match children.iter_mut().find(|child| child.name == count.name) {
+                Some(child) => {
+                    child.max_occurs = child.max_occurs.max(count.max_occurs);
+                    child.recursive |= count.recursive;
+                }
+                None => children.push(ChildField {
+                    name: count.name.clone(),
+                    ..*count
+                }),
+            }
+        }
+        // A child that an instance doesn't have occurs zero times in it.
+        for child in &mut children {
+            child.min_occurs = counts
+                .iter()
+                .map(|counts| {
+                    counts
+                        .iter()
+                        .find(|count| count.name == child.name)
+                        .map_or(0, |count| count.min_occurs)
+                })
+                .min()
+                .unwrap_or(0);
+        }
+
+        ElementFields {
+            name: nodes[0].name.clone(),
+            attributes,
+            children,
+            has_text: nodes.iter().any(|node| node.has_text()),
+            description: nodes.iter().find_map(|node| node.description.clone()),
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use super::Graph;
+use super::tree::ElementTree;
+
+/// Quotes and escapes a string as a JSON string literal.
+pub(super) fn quote(value: &str) -> String {
+    serde_json::to_string(value).expect("Serializing a string can't fail")
+}
+
+impl Graph {
+    /// Serializes the tree as JSON, without the synthetic root.
+    /// A document with a single top-level element becomes an object, otherwise an array of the top-level elements.
+    /// Every element has a `name`, its `attributes`, `text` when it contains text, and its `children`.
+    pub fn to_json(&self) -> String {
+        let tree = self.element_tree();
+        match tree.roots.as_slice() {
+            [] => String::from("[]"),
+            [root] => json_element(&tree, *root, 0),
+            roots => {
+                let elements: Vec<String> = roots
+                    .iter()
+                    .map(|root| format!("  {}", json_element(&tree, *root, 1)))
+                    .collect();
+                format!("[\n{}\n]", elements.join(",\n"))
+            }
+        }
+    }
+}
+
+/// Writes the element as a pretty-printed object that is `depth` levels deep, without the indent of its first line.
+/// Nested objects are queued on a stack together with the separators that go between them.
+fn json_element(tree: &ElementTree, index: usize, depth: usize) -> String {
+    /// The remaining work, an element to write or the text between the elements.
+    enum Step {
+        Open(usize, usize),
+        Text(String),
+    }
+
+    let mut res = String::new();
+    let mut stack = vec![Step::Open(index, depth)];
+    while let Some(step) = stack.pop() {
+        let (index, depth) = match step {
+            Step::Open(index, depth) => (index, depth),
+            Step::Text(text) => {
+                res.push_str(&text);
+                continue;
+            }
+        };
+        let element = &tree.elements[index];
+        let indent = "  ".repeat(depth);
+
+        res.push_str(&format!(
+            "{{\n{}  \"name\": {},\n",
+            indent,
+            quote(element.name)
+        ));
+        if element.attributes.is_empty() {
+            res.push_str(&format!("{}  \"attributes\": [],\n", indent));
+        } else {
+            let keys: Vec<String> = element
+                .attributes
+                .iter()
+                .map(|key| format!("{}    {}", indent, quote(key)))
+                .collect();
+            res.push_str(&format!(
+                "{0}  \"attributes\": [\n{1}\n{0}  ],\n",
+                indent,
+                keys.join(",\n")
+            ));
+        }
+        if element.text {
+            res.push_str(&format!("{}  \"text\": true,\n", indent));
+        }
+
+        if element.children.is_empty() {
+            res.push_str(&format!("{}  \"children\": []\n{}}}", indent, indent));
+            continue;
+        }
+        res.push_str(&format!("{}  \"children\": [\n", indent));
+        stack.push(Step::Text(format!("\n{0}  ]\n{0}}}", indent)));
+        for (position, child) in element.children.iter().enumerate().rev() {
+            stack.push(Step::Open(*child, depth + 2));
+            let separator = if position == 0 { "" } else { ",\n" };
+            stack.push(Step::Text(format!("{}{}    ", separator, indent)));
+        }
+    }
+
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::converter::config::Config;
+    use crate::converter::parser::{lex_graph, lex_tokens};
+
+    #[test]
+    fn test_to_json() {
+        let tokens =
+            lex_tokens("<catalog><book id=\"1\"><title>Text</title></book></catalog>").unwrap();
+        let graph = lex_graph(tokens, &Config::default()).unwrap();
+
+        let json: serde_json::Value = serde_json::from_str(&graph.to_json()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "name": "catalog",
+                "attributes": [],
+                "children": [{
+                    "name": "book",
+                    "attributes": ["id"],
+                    "children": [{
+                        "name": "title",
+                        "attributes": [],
+                        "text": true,
+                        "children": []
+                    }]
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn test_to_json_roots() {
+        let graph = lex_graph(lex_tokens("").unwrap(), &Config::default()).unwrap();
+        assert_eq!(graph.to_json(), "[]");
+
+        let graph = lex_graph(lex_tokens("<a x=\"1\"/><b/>").unwrap(), &Config::default()).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&graph.to_json()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([
+                { "name": "a", "attributes": ["x"], "children": [] },
+                { "name": "b", "attributes": [], "children": [] }
+            ])
+        );
+    }
+}
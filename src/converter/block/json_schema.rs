@@ -0,0 +1,252 @@
+use super::json::quote;
+use super::{Graph, ValueType};
+
+/// The JSON Schema type that matches an inferred value type, with the format that narrows it down.
+fn json_schema_type(value_type: ValueType) -> (&'static str, Option<&'static str>) {
+    match value_type {
+        ValueType::Int => ("integer", None),
+        ValueType::Float => ("number", None),
+        ValueType::Bool => ("boolean", None),
+        ValueType::Date => ("string", Some("date")),
+        ValueType::String => ("string", None),
+    }
+}
+
+/// What a property of an element's object holds.
+enum Property {
+    Value(ValueType),
+    Element(usize, bool),
+}
+
+impl Graph {
+    /// Generates a Draft-07 JSON Schema from the tree, without the synthetic root.
+    /// Every element is an object with a property for each attribute and child, in the same shape as the
+    /// TypeScript interfaces. Repeated children become an array and text is kept in `text`.
+    /// A document with several top-level elements becomes an array of them.
+    pub fn to_json_schema(&self) -> String {
+        let roots = self.child_ids(0);
+        let schema = "\"$schema\": \"http://json-schema.org/draft-07/schema#\"";
+        match roots.as_slice() {
+            [] => format!(
+                "{{\n  {},\n  \"type\": \"array\",\n  \"maxItems\": 0\n}}",
+                schema
+            ),
+            [root] => format!(
+                "{{\n  {},{}",
+                schema,
+                &self.json_schema_element(*root, 0)[1..]
+            ),
+            _ => {
+                let elements: Vec<String> = roots
+                    .iter()
+                    .map(|root| format!("      {}", self.json_schema_element(*root, 3)))
+                    .collect();
+                format!(
+                    "{{\n  {},\n  \"type\": \"array\",\n  \"items\": {{\n    \"anyOf\": [\n{}\n    ]\n  }}\n}}",
+                    schema,
+                    elements.join(",\n")
+                )
+            }
+        }
+    }
+
+    /// Writes the schema of the element as a pretty-printed object that is `depth` levels deep, without the indent
+    /// of its first line. The schemas of the children wait on a stack until the text before them is complete.
+    fn json_schema_element(&self, id: usize, depth: usize) -> String {
+        /// The remaining work, an element to describe or the text between the elements.
+        enum Step {
+            Open(usize, usize),
+            Text(String),
+        }
+
+        let mut res = String::new();
+        let mut stack = vec![Step::Open(id, depth)];
+        while let Some(step) = stack.pop() {
+            let (id, depth) = match step {
+                Step::Open(id, depth) => (id, depth),
+                Step::Text(text) => {
+                    res.push_str(&text);
+                    continue;
+                }
+            };
+            let node = self.get_node(id);
+            let indent = "  ".repeat(depth);
+
+            let mut properties = Vec::<(&str, Property)>::new();
+            let mut required = Vec::<&str>::new();
+            for (key, value_type) in node.keys.iter().zip(&node.types) {
+                properties.push((key, Property::Value(*value_type)));
+                if node
+                    .key_presence(key)
+                    .is_none_or(|presence| presence >= 1.0)
+                {
+                    required.push(key);
+                }
+            }
+            let children: Vec<_> = self.children_of(id).collect();
+            for child in &children {
+                if properties.iter().any(|(name, _)| *name == child.name) {
+                    continue;
+                }
+                let same_name = children.iter().filter(|c| c.name == child.name).count();
+                let array = same_name > 1 || child.max_occurs > 1;
+                properties.push((&child.name, Property::Element(child.id, array)));
+                if child.min_occurs > 0 {
+                    required.push(&child.name);
+                }
+            }
+            if node.has_text() {
+                properties.push(("text", Property::Value(ValueType::String)));
+            }
+
+            // The text up to the next child, children are pushed onto the stack in reverse once it's complete.
+            let mut steps = Vec::<Step>::new();
+            let mut text = format!("{{\n{}  \"type\": \"object\",\n", indent);
+            if let Some(description) = &node.description {
+                text.push_str(&format!(
+                    "{}  \"description\": {},\n",
+                    indent,
+                    quote(description)
+                ));
+            }
+            if properties.is_empty() {
+                text.push_str(&format!("{}  \"properties\": {{}}", indent));
+            } else {
+                text.push_str(&format!("{}  \"properties\": {{\n", indent));
+            }
+            for (index, (name, property)) in properties.iter().enumerate() {
+                let separator = if index == 0 { "" } else { ",\n" };
+                text.push_str(&format!("{}{}    {}: ", separator, indent, quote(name)));
+                match property {
+                    Property::Value(value_type) => {
+                        let (value_type, format) = json_schema_type(*value_type);
+                        text.push_str(&format!("{{\n{}      \"type\": \"{}\"", indent, value_type));
+                        if let Some(format) = format {
+                            text.push_str(&format!(
+                                ",\n{}      \"format\": \"{}\"",
+                                indent, format
+                            ));
+                        }
+                        text.push_str(&format!("\n{}    }}", indent));
+                    }
+                    Property::Element(child_id, false) => {
+                        steps.push(Step::Text(std::mem::take(&mut text)));
+                        steps.push(Step::Open(*child_id, depth + 2));
+                    }
+                    Property::Element(child_id, true) => {
+                        text.push_str(&format!(
+                            "{{\n{0}      \"type\": \"array\",\n{0}      \"items\": ",
+                            indent
+                        ));
+                        steps.push(Step::Text(std::mem::take(&mut text)));
+                        steps.push(Step::Open(*child_id, depth + 3));
+                        text.push_str(&format!("\n{}    }}", indent));
+                    }
+                }
+            }
+            if !properties.is_empty() {
+                text.push_str(&format!("\n{}  }}", indent));
+            }
+            if !required.is_empty() {
+                let required: Vec<String> = required
+                    .iter()
+                    .map(|name| format!("{}    {}", indent, quote(name)))
+                    .collect();
+                text.push_str(&format!(
+                    ",\n{0}  \"required\": [\n{1}\n{0}  ]",
+                    indent,
+                    required.join(",\n")
+                ));
+            }
+            text.push_str(&format!("\n{}}}", indent));
+            steps.push(Step::Text(text));
+
+            stack.extend(steps.into_iter().rev());
+        }
+
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::converter::config::Config;
+    use crate::converter::parser::{lex_graph, lex_tokens};
+
+    #[test]
+    fn test_to_json_schema() {
+        let tokens = lex_tokens(
+            "<catalog><!-- A book --><book id=\"1\" published=\"2024-01-31\"><title>Text</title></book><book id=\"2\" published=\"2024-02-01\"><title>Text</title><cover/></book></catalog>",
+        )
+        .unwrap();
+        let mut graph = lex_graph(tokens, &Config::default()).unwrap();
+        graph.minimize(&Config::default());
+
+        let schema: serde_json::Value = serde_json::from_str(&graph.to_json_schema()).unwrap();
+        assert_eq!(
+            schema,
+            serde_json::json!({
+                "$schema": "http://json-schema.org/draft-07/schema#",
+                "type": "object",
+                "properties": {
+                    "book": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "description": "A book",
+                            "properties": {
+                                "id": { "type": "integer" },
+                                "published": { "type": "string", "format": "date" },
+                                "title": {
+                                    "type": "object",
+                                    "properties": {
+                                        "text": { "type": "string" }
+                                    }
+                                },
+                                "cover": {
+                                    "type": "object",
+                                    "properties": {}
+                                }
+                            },
+                            "required": ["id", "published", "title"]
+                        }
+                    }
+                },
+                "required": ["book"]
+            })
+        );
+    }
+
+    #[test]
+    fn test_to_json_schema_several_roots() {
+        let tokens = lex_tokens("<a x=\"1.5\"/><b/>").unwrap();
+        let graph = lex_graph(tokens, &Config::default()).unwrap();
+
+        assert_eq!(
+            graph.to_json_schema(),
+            r#"{
+  "$schema": "http://json-schema.org/draft-07/schema#",
+  "type": "array",
+  "items": {
+    "anyOf": [
+      {
+        "type": "object",
+        "properties": {
+          "x": {
+            "type": "number"
+          }
+        },
+        "required": [
+          "x"
+        ]
+      },
+      {
+        "type": "object",
+        "properties": {}
+      }
+    ]
+  }
+}"#
+        );
+    }
+}
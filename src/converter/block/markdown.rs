@@ -0,0 +1,104 @@
+use super::Graph;
+
+/// Escapes the characters that would end a cell of a pipe table.
+fn escape(value: &str) -> String {
+    value.replace('|', "\\|")
+}
+
+/// A distinct element, combined over every position it appears in.
+struct Row {
+    name: String,
+    attributes: Vec<String>,
+    children: Vec<String>,
+}
+
+impl Graph {
+    /// Renders a GitHub-flavored Markdown table with a row for every distinct element in the tree,
+    /// listing the attributes and children it has anywhere in the document.
+    pub fn to_markdown(&self) -> String {
+        let mut rows = Vec::<Row>::new();
+
+        let mut stack: Vec<usize> = self.child_ids(0).into_iter().rev().collect();
+        while let Some(id) = stack.pop() {
+            let node = self.get_node(id);
+            let child_ids = self.child_ids(id);
+
+            let index = match rows.iter().position(|row| row.name == node.name) {
+                Some(index) => index,
+                None => {
+                    rows.push(Row {
+                        name: node.name.clone(),
+                        attributes: Vec::new(),
+                        children: Vec::new(),
+                    });
+                    rows.len() - 1
+                }
+            };
+            let row = &mut rows[index];
+            for key in &node.keys {
+                if !row.attributes.contains(key) {
+                    row.attributes.push(key.clone());
+                }
+            }
+            for child_id in &child_ids {
+                let name = &self.get_node(*child_id).name;
+                if !row.children.contains(name) {
+                    row.children.push(name.clone());
+                }
+            }
+
+            stack.extend(child_ids.into_iter().rev());
+        }
+
+        let mut res = String::from("| Element | Attributes | Children |\n| --- | --- | --- |");
+        for row in rows {
+            let list = |names: &[String]| {
+                names
+                    .iter()
+                    .map(|name| escape(name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            res.push_str(&format!(
+                "\n| {} | {} | {} |",
+                escape(&row.name),
+                list(&row.attributes),
+                list(&row.children)
+            ));
+        }
+
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::converter::config::Config;
+    use crate::converter::parser::{lex_graph, lex_tokens};
+
+    #[test]
+    fn test_to_markdown() {
+        let tokens = lex_tokens(
+            "<catalog><book id=\"1\"><title/><note/></book><magazine><title lang=\"en\"/></magazine><book id=\"2\" lang=\"nl\"/></catalog>",
+        )
+        .unwrap();
+        let mut graph = lex_graph(tokens, &Config::default()).unwrap();
+        graph.minimize(&Config::default());
+
+        assert_eq!(
+            graph.to_markdown(),
+            "| Element | Attributes | Children |\n\
+            | --- | --- | --- |\n\
+            | catalog |  | book, magazine |\n\
+            | book | id, lang | title, note |\n\
+            | title | lang |  |\n\
+            | note |  |  |\n\
+            | magazine |  | title |"
+        );
+    }
+
+    #[test]
+    fn test_to_markdown_escapes_pipes() {
+        assert_eq!(super::escape("a|b"), "a\\|b");
+    }
+}
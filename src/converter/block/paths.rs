@@ -0,0 +1,135 @@
+use std::collections::HashSet;
+
+use super::{Graph, NodeOrToken};
+
+impl Graph {
+    /// Lists every distinct path from a top-level element to an element, like `/catalog/book/author`,
+    /// in document order. The attributes of an element follow its path as `/catalog/book/@id`.
+    pub fn to_paths(&self) -> Vec<String> {
+        let mut paths = Vec::new();
+        let mut seen = HashSet::new();
+        let mut push = |path: String| {
+            if seen.insert(path.clone()) {
+                paths.push(path);
+            }
+        };
+        // Every element is paired with the length of its parent's path, the path of the element that was walked
+        // before it always starts with that.
+        let mut path = String::new();
+        let mut stack: Vec<(usize, usize)> = self
+            .child_ids(0)
+            .into_iter()
+            .rev()
+            .map(|id| (id, 0))
+            .collect();
+        while let Some((id, parent_len)) = stack.pop() {
+            let node = self.get_node(id);
+            path.truncate(parent_len);
+            path.push('/');
+            path.push_str(&node.name);
+            push(path.clone());
+            for key in &node.keys {
+                push(format!("{}/@{}", path, key));
+            }
+
+            stack.extend(
+                self.child_ids(id)
+                    .into_iter()
+                    .rev()
+                    .map(|child_id| (child_id, path.len())),
+            );
+        }
+
+        paths
+    }
+
+    /// Lists every distinct path to a leaf element, an element without element children, like
+    /// `catalog/book/price`, in document order. Unlike `to_paths`, the elements that lead to a leaf aren't listed.
+    pub fn to_leaf_paths(&self) -> Vec<String> {
+        let mut paths = Vec::new();
+        let mut seen = HashSet::new();
+        // Like in `to_paths`, the path is shared and cut back to the parent's path before every element.
+        let mut path = String::new();
+        let mut stack: Vec<(usize, usize)> = self
+            .child_ids(0)
+            .into_iter()
+            .rev()
+            .map(|id| (id, 0))
+            .collect();
+        while let Some((id, parent_len)) = stack.pop() {
+            let node = self.get_node(id);
+            path.truncate(parent_len);
+            if parent_len > 0 {
+                path.push('/');
+            }
+            path.push_str(&node.name);
+
+            let is_leaf = !node
+                .children
+                .iter()
+                .any(|child| matches!(child, NodeOrToken::N(_)));
+            if is_leaf {
+                if seen.insert(path.clone()) {
+                    paths.push(path.clone());
+                }
+                continue;
+            }
+
+            stack.extend(
+                self.child_ids(id)
+                    .into_iter()
+                    .rev()
+                    .map(|child_id| (child_id, path.len())),
+            );
+        }
+
+        paths
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::converter::config::Config;
+    use crate::converter::parser::{lex_graph, lex_tokens};
+
+    #[test]
+    fn test_to_paths() {
+        let tokens = lex_tokens(
+            "<catalog><book id=\"1\"><author/></book><book id=\"2\" lang=\"en\"><author/><title/></book></catalog>",
+        )
+        .unwrap();
+        let graph = lex_graph(tokens, &Config::default()).unwrap();
+
+        assert_eq!(
+            graph.to_paths(),
+            vec![
+                "/catalog",
+                "/catalog/book",
+                "/catalog/book/@id",
+                "/catalog/book/author",
+                "/catalog/book/@lang",
+                "/catalog/book/title",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_to_leaf_paths() {
+        let tokens = lex_tokens(
+            "<catalog><book id=\"1\"><title>A</title><price>1</price></book>\
+            <book><title>B</title><author><name>C</name></author></book><cover/></catalog>",
+        )
+        .unwrap();
+        let graph = lex_graph(tokens, &Config::default()).unwrap();
+
+        assert_eq!(
+            graph.to_leaf_paths(),
+            vec![
+                "catalog/book/title",
+                "catalog/book/price",
+                "catalog/book/author/name",
+                "catalog/cover",
+            ]
+        );
+    }
+}
@@ -0,0 +1,210 @@
+use super::{Graph, ValueType, pascal_case, words};
+
+/// Rust keywords that can't be used as an identifier without the `r#` prefix.
+const KEYWORDS: [&str; 48] = [
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let",
+    "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+    "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+    "virtual", "where", "while", "yield",
+];
+
+/// Keywords that can't be used as an identifier, not even as a raw one.
+const RESERVED: [&str; 4] = ["crate", "self", "super", "Self"];
+
+/// Turns the name into a valid identifier, prefixing it when it starts with a digit or is a keyword.
+fn identifier(name: String, fallback: &str) -> String {
+    if name.is_empty() {
+        String::from(fallback)
+    } else if name.starts_with(|c: char| c.is_ascii_digit()) || RESERVED.contains(&name.as_str()) {
+        format!("_{}", name)
+    } else if KEYWORDS.contains(&name.as_str()) {
+        format!("r#{}", name)
+    } else {
+        name
+    }
+}
+
+/// The snake case field name of an attribute or child, like `data_id` for `data-id`.
+fn field_name(name: &str) -> String {
+    identifier(words(name).join("_"), "field")
+}
+
+/// The pascal case struct name of an element, like `BookItem` for `book-item`.
+fn struct_name(name: &str) -> String {
+    identifier(pascal_case(name), "Element")
+}
+
+/// The Rust type that matches an inferred value type.
+fn rust_type(value_type: ValueType) -> &'static str {
+    match value_type {
+        ValueType::Int => "i64",
+        ValueType::Float => "f64",
+        ValueType::Bool => "bool",
+        ValueType::Date | ValueType::String => "String",
+    }
+}
+
+impl Graph {
+    /// Generates serde structs for the tree, one for every distinct element name in the order they appear.
+    /// Attributes are renamed to `@key` and text to `$text`, like quick-xml expects. Repeated children
+    /// become a `Vec`, optional ones an `Option`. It's a starting point rather than a finished model.
+    pub fn to_rust(&self) -> String {
+        let mut res = String::from("use serde::Deserialize;\n");
+        for element in self.element_fields() {
+            let mut fields = Vec::<(Vec<String>, String, String)>::new();
+            for attribute in &element.attributes {
+                let mut attributes = vec![format!("rename = \"@{}\"", attribute.name)];
+                let mut field_type = String::from(rust_type(attribute.value_type));
+                if attribute.optional {
+                    attributes.push(String::from("default"));
+                    field_type = format!("Option<{}>", field_type);
+                }
+                fields.push((attributes, field_name(&attribute.name), field_type));
+            }
+            for child in &element.children {
+                let name = field_name(&child.name);
+                let mut attributes = Vec::new();
+                if name.trim_start_matches("r#") != child.name {
+                    attributes.push(format!("rename = \"{}\"", child.name));
+                }
+                let child_type = struct_name(&child.name);
+                let child_type = if child.max_occurs > 1 {
+                    format!("Vec<{}>", child_type)
+                } else if child.recursive {
+                    format!("Box<{}>", child_type)
+                } else {
+                    child_type
+                };
+                let child_type = if child.max_occurs <= 1 && child.min_occurs == 0 {
+                    format!("Option<{}>", child_type)
+                } else {
+                    child_type
+                };
+                if child.max_occurs > 1 || child.min_occurs == 0 {
+                    attributes.push(String::from("default"));
+                }
+                fields.push((attributes, name, child_type));
+            }
+            if element.has_text {
+                fields.push((
+                    vec![String::from("rename = \"$text\""), String::from("default")],
+                    String::from("text"),
+                    String::from("String"),
+                ));
+            }
+
+            res.push_str(&format!(
+                "\n#[derive(Debug, Deserialize)]\npub struct {} {{",
+                struct_name(&element.name)
+            ));
+            if !fields.is_empty() {
+                res.push('\n');
+            }
+            for (attributes, name, field_type) in fields {
+                if !attributes.is_empty() {
+                    res.push_str(&format!("    #[serde({})]\n", attributes.join(", ")));
+                }
+                res.push_str(&format!("    pub {}: {},\n", name, field_type));
+            }
+            res.push_str("}\n");
+        }
+
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{field_name, struct_name};
+    use crate::converter::config::Config;
+    use crate::converter::parser::{lex_graph, lex_tokens};
+
+    #[test]
+    fn test_identifiers() {
+        assert_eq!(field_name("data-id"), "data_id");
+        assert_eq!(field_name("xml:lang"), "xml_lang");
+        assert_eq!(field_name("createdAt"), "created_at");
+        assert_eq!(field_name("type"), "r#type");
+        assert_eq!(field_name("use"), "r#use");
+        assert_eq!(field_name("where"), "r#where");
+        assert_eq!(field_name("self"), "_self");
+        assert_eq!(field_name("2nd"), "_2nd");
+        assert_eq!(field_name("-"), "field");
+        assert_eq!(struct_name("book-item"), "BookItem");
+        assert_eq!(struct_name("dc:title"), "DcTitle");
+        assert_eq!(struct_name("URL"), "Url");
+    }
+
+    #[test]
+    fn test_to_rust() {
+        let tokens = lex_tokens(
+            "<catalog><book id=\"1\" data-lang=\"en\"><title>Text</title></book><book id=\"2\" data-lang=\"nl\"><title>Text</title><cover/></book></catalog>",
+        )
+        .unwrap();
+        let mut graph = lex_graph(tokens, &Config::default()).unwrap();
+        graph.minimize(&Config::default());
+
+        assert_eq!(
+            graph.to_rust(),
+            r#"use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct Catalog {
+    #[serde(default)]
+    pub book: Vec<Book>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Book {
+    #[serde(rename = "@id")]
+    pub id: i64,
+    #[serde(rename = "@data-lang")]
+    pub data_lang: String,
+    pub title: Title,
+    #[serde(default)]
+    pub cover: Option<Cover>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Title {
+    #[serde(rename = "$text", default)]
+    pub text: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Cover {}
+"#
+        );
+    }
+
+    #[test]
+    fn test_to_rust_recursive() {
+        let tokens = lex_tokens("<section><section/></section>").unwrap();
+        let graph = lex_graph(tokens, &Config::default()).unwrap();
+
+        assert!(graph.to_rust().contains(
+            "pub struct Section {\n    #[serde(default)]\n    pub section: Option<Box<Section>>,\n}"
+        ));
+    }
+
+    #[test]
+    fn test_to_rust_combines_instances() {
+        let tokens = lex_tokens(
+            "<catalog><book id=\"1\"><title/></book><book id=\"2\" lang=\"en\"><title/><cover/></book></catalog>",
+        )
+        .unwrap();
+        let graph = lex_graph(tokens, &Config::default()).unwrap();
+
+        assert!(
+            graph.to_rust().contains(
+                "pub struct Catalog {\n    #[serde(default)]\n    pub book: Vec<Book>,\n}"
+            )
+        );
+        assert!(graph.to_rust().contains(
+            "pub struct Book {\n    #[serde(rename = \"@id\")]\n    pub id: i64,\n    \
+            #[serde(rename = \"@lang\", default)]\n    pub lang: Option<String>,\n    pub title: Title,\n    \
+            #[serde(default)]\n    pub cover: Option<Cover>,\n}"
+        ));
+    }
+}
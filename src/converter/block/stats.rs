@@ -0,0 +1,294 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use super::Graph;
+
+/// The number of different values an attribute of an element has in the document.
+#[derive(Debug, PartialEq, Eq)]
+pub struct AttributeCardinality {
+    pub element: String,
+    pub attribute: String,
+    pub distinct_values: usize,
+}
+
+/// Summary metrics of a document.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Stats {
+    /// The number of elements.
+    pub elements: usize,
+    /// The number of distinct element names.
+    pub distinct_elements: usize,
+    /// The length of the longest chain of nested elements.
+    pub max_depth: usize,
+    /// The number of elements at every depth, starting with the top-level elements at depth 0.
+    pub depth_histogram: Vec<usize>,
+    /// The number of distinct values of every attribute, in the order they first appear.
+    pub attributes: Vec<AttributeCardinality>,
+    /// The names of the elements that occur inside an element with the same name.
+    pub recursive_elements: Vec<String>,
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Elements: {}", self.elements)?;
+        writeln!(f, "Distinct elements: {}", self.distinct_elements)?;
+        write!(f, "Maximum depth: {}", self.max_depth)?;
+        if !self.depth_histogram.is_empty() {
+            let depths: Vec<String> = self
+                .depth_histogram
+                .iter()
+                .enumerate()
+                .map(|(depth, count)| format!("depth {}: {}", depth, count))
+                .collect();
+            write!(f, "\nElements per depth: {}", depths.join(", "))?;
+        }
+        if !self.recursive_elements.is_empty() {
+            write!(
+                f,
+                "\nRecursive elements: {}",
+                self.recursive_elements.join(", ")
+            )?;
+        }
+        if !self.attributes.is_empty() {
+            write!(f, "\nDistinct attribute values:")?;
+        }
+        for attribute in &self.attributes {
+            let values = if attribute.distinct_values == 1 {
+                "value"
+            } else {
+                "values"
+            };
+            write!(
+                f,
+                "\n  {}/@{}: {} distinct {}",
+                attribute.element, attribute.attribute, attribute.distinct_values, values
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Graph {
+    /// The number of elements in the longest chain from a top-level element to a leaf, not counting the synthetic root.
+    pub fn max_depth(&self) -> usize {
+        let mut max_depth = 0;
+        let mut stack = vec![(0, 0)];
+        while let Some((id, depth)) = stack.pop() {
+            max_depth = max_depth.max(depth);
+            stack.extend(
+                self.child_ids(id)
+                    .into_iter()
+                    .map(|child_id| (child_id, depth + 1)),
+            );
+        }
+
+        max_depth
+    }
+
+    /// The number of elements at every depth, indexed by depth with the top-level elements at depth 0.
+    /// Minimizing collapses repeated elements, so call it before minimizing for the counts of the document.
+    pub fn depth_histogram(&self) -> Vec<usize> {
+        let mut histogram = Vec::<usize>::new();
+        let mut stack: Vec<(usize, usize)> =
+            self.child_ids(0).into_iter().map(|id| (id, 0)).collect();
+        while let Some((id, depth)) = stack.pop() {
+            if histogram.len() <= depth {
+                histogram.resize(depth + 1, 0);
+            }
+            histogram[depth] += 1;
+            stack.extend(
+                self.child_ids(id)
+                    .into_iter()
+                    .map(|child_id| (child_id, depth + 1)),
+            );
+        }
+
+        histogram
+    }
+
+    /// Returns the names of the elements that occur as their own ancestor, in the order they are found.
+    pub fn recursive_elements(&self) -> Vec<String> {
+        let mut names = Vec::<String>::new();
+        for node in self.nodes.iter().filter(|node| node.parent.is_some()) {
+            if !names.contains(&node.name) && node.is_recursive(self) {
+                names.push(node.name.clone());
+            }
+        }
+
+        names
+    }
+
+    /// Counts the distinct values of every attribute over all instances of its element.
+    /// Minimizing only keeps one sample value, so call it before minimizing.
+    pub fn attribute_cardinality(&self) -> Vec<AttributeCardinality> {
+        let mut values = Vec::<(&str, &str, HashSet<&str>)>::new();
+        let mut index_of = HashMap::<(&str, &str), usize>::new();
+        for node in self.iter() {
+            for (key, value) in node.keys.iter().zip(&node.values) {
+                match index_of.get(&(node.name.as_str(), key.as_str())) {
+                    Some(&index) => {
+                        values[index].2.insert(value);
+                    }
+                    None => {
+                        index_of.insert((&node.name, key), values.len());
+                        values.push((&node.name, key, HashSet::from([value.as_str()])));
+                    }
+                }
+            }
+        }
+
+        values
+            .into_iter()
+            .map(|(element, attribute, seen)| AttributeCardinality {
+                element: String::from(element),
+                attribute: String::from(attribute),
+                distinct_values: seen.len(),
+            })
+            .collect()
+    }
+
+    /// Counts how many times every element name occurs, the most frequent first and ties in the order the names
+    /// first appear. Minimizing collapses repeated elements, so call it before minimizing.
+    pub fn name_frequencies(&self) -> Vec<(String, usize)> {
+        let mut frequencies = Vec::<(String, usize)>::new();
+        let mut index_of = HashMap::<&str, usize>::new();
+        for node in self.iter() {
+            match index_of.get(node.name.as_str()) {
+                Some(&index) => frequencies[index].1 += 1,
+                None => {
+                    index_of.insert(&node.name, frequencies.len());
+                    frequencies.push((node.name.clone(), 1));
+                }
+            }
+        }
+        frequencies.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+        frequencies
+    }
+
+    /// Computes the summary metrics of the elements that are reachable from the root.
+    /// Call it before minimizing to describe the document instead of the overview.
+    pub fn stats(&self) -> Stats {
+        let mut names = Vec::<&str>::new();
+        let mut elements = 0;
+        for node in self.iter() {
+            elements += 1;
+            if !names.contains(&node.name.as_str()) {
+                names.push(&node.name);
+            }
+        }
+
+        Stats {
+            elements,
+            distinct_elements: names.len(),
+            max_depth: self.max_depth(),
+            depth_histogram: self.depth_histogram(),
+            attributes: self.attribute_cardinality(),
+            recursive_elements: self.recursive_elements(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AttributeCardinality, Stats};
+    use crate::converter::config::Config;
+    use crate::converter::parser::{lex_graph, lex_tokens};
+
+    #[test]
+    fn test_stats() {
+        let tokens = lex_tokens("<a>text<b><c/></b><b/><d><b/></d></a>").unwrap();
+        let graph = lex_graph(tokens, &Config::default()).unwrap();
+
+        assert_eq!(graph.max_depth(), 3);
+        assert_eq!(
+            graph.stats(),
+            Stats {
+                elements: 6,
+                distinct_elements: 4,
+                max_depth: 3,
+                depth_histogram: vec![1, 3, 2],
+                attributes: Vec::new(),
+                recursive_elements: Vec::new(),
+            }
+        );
+        assert_eq!(
+            graph.stats().to_string(),
+            "Elements: 6\nDistinct elements: 4\nMaximum depth: 3\n\
+            Elements per depth: depth 0: 1, depth 1: 3, depth 2: 2"
+        );
+    }
+
+    #[test]
+    fn test_attribute_cardinality() {
+        let tokens = lex_tokens(
+            "<orders><order id=\"1\" status=\"open\"/><order id=\"2\" status=\"done\"/><order id=\"3\" status=\"open\"/></orders>",
+        )
+        .unwrap();
+        let graph = lex_graph(tokens, &Config::default()).unwrap();
+
+        assert_eq!(
+            graph.attribute_cardinality(),
+            vec![
+                AttributeCardinality {
+                    element: String::from("order"),
+                    attribute: String::from("id"),
+                    distinct_values: 3,
+                },
+                AttributeCardinality {
+                    element: String::from("order"),
+                    attribute: String::from("status"),
+                    distinct_values: 2,
+                },
+            ]
+        );
+        assert!(graph.stats().to_string().ends_with(
+            "Distinct attribute values:\n  order/@id: 3 distinct values\n  order/@status: 2 distinct values"
+        ));
+    }
+
+    #[test]
+    fn test_recursive_elements() {
+        let tokens =
+            lex_tokens("<doc><section><section><list><list/></list></section></section></doc>")
+                .unwrap();
+        let graph = lex_graph(tokens, &Config::default()).unwrap();
+
+        assert_eq!(graph.recursive_elements(), vec!["section", "list"]);
+        assert!(
+            graph
+                .stats()
+                .to_string()
+                .ends_with("Recursive elements: section, list")
+        );
+    }
+
+    #[test]
+    fn test_name_frequencies() {
+        let tokens = lex_tokens("<a><b/><c/><b><c/></b><d/><b/></a>").unwrap();
+        let graph = lex_graph(tokens, &Config::default()).unwrap();
+
+        assert_eq!(
+            graph.name_frequencies(),
+            vec![
+                (String::from("b"), 3),
+                (String::from("c"), 2),
+                (String::from("a"), 1),
+                (String::from("d"), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_max_depth_empty() {
+        let graph = lex_graph(lex_tokens("").unwrap(), &Config::default()).unwrap();
+
+        assert_eq!(graph.max_depth(), 0);
+        assert!(graph.depth_histogram().is_empty());
+        assert_eq!(
+            graph.stats().to_string(),
+            "Elements: 0\nDistinct elements: 0\nMaximum depth: 0"
+        );
+    }
+}
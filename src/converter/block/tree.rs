@@ -0,0 +1,56 @@
+use super::Graph;
+
+/// An element of the tree that the JSON and YAML output are written from.
+pub(super) struct Element<'a> {
+    pub name: &'a str,
+    pub attributes: &'a [String],
+    pub text: bool,
+    /// The indices of the children in [`ElementTree::elements`].
+    pub children: Vec<usize>,
+}
+
+/// The minimized tree without the synthetic root, reduced to what the JSON and YAML output show, so the two agree.
+/// The elements are kept in a flat list in document order, so neither building nor dropping the tree recurses.
+pub(super) struct ElementTree<'a> {
+    /// The indices of the top-level elements in `elements`.
+    pub roots: Vec<usize>,
+    pub elements: Vec<Element<'a>>,
+}
+
+impl Graph {
+    /// Collects the name, attributes, text and children of every element that is reachable from the root.
+    pub(super) fn element_tree(&self) -> ElementTree<'_> {
+        let mut tree = ElementTree {
+            roots: Vec::new(),
+            elements: Vec::new(),
+        };
+        let mut stack: Vec<(usize, Option<usize>)> = self
+            .child_ids(0)
+            .into_iter()
+            .rev()
+            .map(|id| (id, None))
+            .collect();
+        while let Some((id, parent)) = stack.pop() {
+            let node = self.get_node(id);
+            let index = tree.elements.len();
+            tree.elements.push(Element {
+                name: &node.name,
+                attributes: &node.keys,
+                text: node.has_text(),
+                children: Vec::new(),
+            });
+            match parent {
+                Some(parent) => tree.elements[parent].children.push(index),
+                None => tree.roots.push(index),
+            }
+            stack.extend(
+                self.child_ids(id)
+                    .into_iter()
+                    .rev()
+                    .map(|child_id| (child_id, Some(index))),
+            );
+        }
+
+        tree
+    }
+}
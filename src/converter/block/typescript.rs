@@ -0,0 +1,159 @@
+use super::{Graph, ValueType, pascal_case};
+
+/// Whether the name can be used as a property key without quotes.
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+}
+
+/// The property key of an attribute or child, quoted when it isn't a valid identifier.
+fn property_key(name: &str) -> String {
+    if is_identifier(name) {
+        String::from(name)
+    } else {
+        format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+}
+
+/// The pascal case interface name of an element, like `BookItem` for `book-item`.
+fn interface_name(name: &str) -> String {
+    let name = pascal_case(name);
+    if name.is_empty() {
+        String::from("Element")
+    } else if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", name)
+    } else {
+        name
+    }
+}
+
+/// The TypeScript type that matches an inferred value type.
+fn typescript_type(value_type: ValueType) -> &'static str {
+    match value_type {
+        ValueType::Int | ValueType::Float => "number",
+        ValueType::Bool => "boolean",
+        ValueType::Date | ValueType::String => "string",
+    }
+}
+
+impl Graph {
+    /// Generates TypeScript interfaces for the tree, one for every distinct element name in the order they appear.
+    /// Repeated children become an array and optional ones an optional property, text is kept in `text`.
+    pub fn to_typescript(&self) -> String {
+        let mut interfaces = Vec::<String>::new();
+        for element in self.element_fields() {
+            let mut res = String::new();
+            for attribute in &element.attributes {
+                res.push_str(&format!(
+                    "  {}{}: {};\n",
+                    property_key(&attribute.name),
+                    if attribute.optional { "?" } else { "" },
+                    typescript_type(attribute.value_type)
+                ));
+            }
+            for child in &element.children {
+                let (optional, array) = match (child.min_occurs, child.max_occurs) {
+                    (_, 2..) => ("", "[]"),
+                    (0, _) => ("?", ""),
+                    _ => ("", ""),
+                };
+                res.push_str(&format!(
+                    "  {}{}: {}{};\n",
+                    property_key(&child.name),
+                    optional,
+                    interface_name(&child.name),
+                    array
+                ));
+            }
+            if element.has_text {
+                res.push_str("  text: string;\n");
+            }
+            interfaces.push(format!(
+                "export interface {} {{{}{}}}",
+                interface_name(&element.name),
+                if res.is_empty() { "" } else { "\n" },
+                res
+            ));
+        }
+
+        interfaces.join("\n\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{interface_name, property_key};
+    use crate::converter::config::Config;
+    use crate::converter::parser::{lex_graph, lex_tokens};
+
+    #[test]
+    fn test_names() {
+        assert_eq!(property_key("id"), "id");
+        assert_eq!(property_key("data-id"), "\"data-id\"");
+        assert_eq!(property_key("xml:lang"), "\"xml:lang\"");
+        assert_eq!(interface_name("book-item"), "BookItem");
+        assert_eq!(interface_name("2nd"), "_2nd");
+        assert_eq!(interface_name("-"), "Element");
+    }
+
+    #[test]
+    fn test_to_typescript() {
+        let tokens = lex_tokens(
+            "<catalog><book id=\"1\" data-lang=\"en\" sold=\"true\"><title>Text</title></book><book id=\"2\" data-lang=\"nl\" sold=\"false\"><title>Text</title><cover/></book></catalog>",
+        )
+        .unwrap();
+        let mut graph = lex_graph(tokens, &Config::default()).unwrap();
+        graph.minimize(&Config::default());
+
+        assert_eq!(
+            graph.to_typescript(),
+            r#"export interface Catalog {
+  book: Book[];
+}
+
+export interface Book {
+  id: number;
+  "data-lang": string;
+  sold: boolean;
+  title: Title;
+  cover?: Cover;
+}
+
+export interface Title {
+  text: string;
+}
+
+export interface Cover {}"#
+        );
+    }
+
+    #[test]
+    fn test_to_typescript_combines_instances() {
+        let tokens = lex_tokens(
+            "<catalog><book id=\"1\"><title/></book><book id=\"2\" lang=\"en\"><title/><cover/></book></catalog>",
+        )
+        .unwrap();
+        let graph = lex_graph(tokens, &Config::default()).unwrap();
+
+        assert_eq!(
+            graph.to_typescript(),
+            r#"export interface Catalog {
+  book: Book[];
+}
+
+export interface Book {
+  id: number;
+  lang?: string;
+  title: Title;
+  cover?: Cover;
+}
+
+export interface Title {}
+
+export interface Cover {}"#
+        );
+    }
+}
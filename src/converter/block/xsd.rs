@@ -0,0 +1,160 @@
+use super::fields::AttributeField;
+use super::{Graph, ValueType};
+
+/// Escapes the characters that can't appear literally in XML text.
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// The built-in XSD type that matches an inferred value type.
+fn xsd_type(value_type: ValueType) -> &'static str {
+    match value_type {
+        ValueType::Int => "xs:integer",
+        ValueType::Float => "xs:decimal",
+        ValueType::Bool => "xs:boolean",
+        ValueType::Date => "xs:date",
+        ValueType::String => "xs:string",
+    }
+}
+
+impl Graph {
+    /// Generates a rough XSD schema from the tree, without the synthetic root.
+    /// Every distinct element is declared once at the top level, with an anonymous complex type that refers to
+    /// its children in a sequence. Attributes get the type inferred from their values and fall back to
+    /// `xs:string`, namespace declarations aren't attributes in a schema so they're left out.
+    pub fn to_xsd(&self) -> String {
+        let mut res = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+            <xs:schema xmlns:xs=\"http://www.w3.org/2001/XMLSchema\">\n",
+        );
+        for element in self.element_fields() {
+            let attributes: Vec<&AttributeField> = element
+                .attributes
+                .iter()
+                .filter(|attribute| {
+                    attribute.name != "xmlns" && !attribute.name.starts_with("xmlns:")
+                })
+                .collect();
+
+            // Elements with only text don't need a complex type.
+            if element.children.is_empty() && attributes.is_empty() && element.description.is_none()
+            {
+                let element_type = if element.has_text {
+                    " type=\"xs:string\""
+                } else {
+                    ""
+                };
+                res.push_str(&format!(
+                    "  <xs:element name=\"{}\"{}/>\n",
+                    element.name, element_type
+                ));
+                continue;
+            }
+
+            res.push_str(&format!("  <xs:element name=\"{}\">\n", element.name));
+            if let Some(description) = &element.description {
+                res.push_str(&format!(
+                    "    <xs:annotation>\n      <xs:documentation>{}</xs:documentation>\n    </xs:annotation>\n",
+                    escape(description)
+                ));
+            }
+
+            let mixed = if element.has_text {
+                " mixed=\"true\""
+            } else {
+                ""
+            };
+            res.push_str(&format!("    <xs:complexType{}>\n", mixed));
+            if !element.children.is_empty() {
+                res.push_str("      <xs:sequence>\n");
+                for child in &element.children {
+                    let mut occurs = String::new();
+                    if child.min_occurs == 0 {
+                        occurs.push_str(" minOccurs=\"0\"");
+                    }
+                    if child.max_occurs > 1 {
+                        occurs.push_str(" maxOccurs=\"unbounded\"");
+                    }
+                    res.push_str(&format!(
+                        "        <xs:element ref=\"{}\"{}/>\n",
+                        child.name, occurs
+                    ));
+                }
+                res.push_str("      </xs:sequence>\n");
+            }
+            for attribute in attributes {
+                res.push_str(&format!(
+                    "      <xs:attribute name=\"{}\" type=\"{}\"/>\n",
+                    attribute.name,
+                    xsd_type(attribute.value_type)
+                ));
+            }
+            res.push_str("    </xs:complexType>\n  </xs:element>\n");
+        }
+        res.push_str("</xs:schema>");
+
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::converter::config::Config;
+    use crate::converter::parser::{lex_graph, lex_tokens};
+
+    #[test]
+    fn test_to_xsd() {
+        let tokens = lex_tokens(
+            "<catalog><!-- A book --><book id=\"1\" lang=\"en\"><title>Text</title><cover/></book><book id=\"2\" lang=\"nl\"/></catalog>",
+        )
+        .unwrap();
+        let mut graph = lex_graph(tokens, &Config::default()).unwrap();
+        graph.minimize(&Config::default());
+
+        assert_eq!(
+            graph.to_xsd(),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">
+  <xs:element name="catalog">
+    <xs:complexType>
+      <xs:sequence>
+        <xs:element ref="book" maxOccurs="unbounded"/>
+      </xs:sequence>
+    </xs:complexType>
+  </xs:element>
+  <xs:element name="book">
+    <xs:annotation>
+      <xs:documentation>A book</xs:documentation>
+    </xs:annotation>
+    <xs:complexType>
+      <xs:sequence>
+        <xs:element ref="title" minOccurs="0"/>
+        <xs:element ref="cover" minOccurs="0"/>
+      </xs:sequence>
+      <xs:attribute name="id" type="xs:integer"/>
+      <xs:attribute name="lang" type="xs:string"/>
+    </xs:complexType>
+  </xs:element>
+  <xs:element name="title" type="xs:string"/>
+  <xs:element name="cover"/>
+</xs:schema>"#
+        );
+    }
+
+    #[test]
+    fn test_to_xsd_declares_elements_once() {
+        let tokens = lex_tokens(
+            "<root xmlns=\"urn:a\" xmlns:x=\"urn:x\"><a><name/></a><b><name/><name/></b></root>",
+        )
+        .unwrap();
+        let xsd = lex_graph(tokens, &Config::default()).unwrap().to_xsd();
+
+        assert_eq!(xsd.matches("<xs:element name=\"name\"").count(), 1);
+        assert!(xsd.contains("<xs:element ref=\"name\" maxOccurs=\"unbounded\"/>"));
+        assert!(!xsd.contains("xmlns=\"urn"));
+        assert!(!xsd.contains("<xs:attribute"));
+    }
+}
@@ -0,0 +1,132 @@
+use super::Graph;
+use super::tree::ElementTree;
+
+/// Quotes a string when it would otherwise be read as something else than a plain string by YAML.
+fn quote(value: &str) -> String {
+    let keywords = ["true", "false", "yes", "no", "on", "off", "null", "~"];
+    let needs_quotes = value.is_empty()
+        || value.starts_with(|c: char| c.is_whitespace() || "-?:,[]{}#&*!|>'\"%@`".contains(c))
+        || value.ends_with(char::is_whitespace)
+        || value.contains(": ")
+        || value.contains(" #")
+        || value.ends_with(':')
+        || keywords.contains(&value.to_lowercase().as_str())
+        || value.parse::<f64>().is_ok();
+
+    if needs_quotes {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        String::from(value)
+    }
+}
+
+impl Graph {
+    /// Renders the tree as YAML, without the synthetic root, from the same elements as [`Graph::to_json`].
+    /// Like there, a single top-level element becomes a mapping, several become a sequence, and none an empty sequence.
+    pub fn to_yaml(&self) -> String {
+        let tree = self.element_tree();
+        let mut res = String::new();
+        match tree.roots.as_slice() {
+            [] => res.push_str("[]"),
+            [root] => yaml_element(&tree, *root, 0, &mut res),
+            roots => {
+                for root in roots {
+                    res.push_str("- ");
+                    yaml_element(&tree, *root, 1, &mut res);
+                }
+            }
+        }
+
+        res.truncate(res.trim_end().len());
+        res
+    }
+}
+
+/// Writes an element as a mapping from its name to its `attrs`, `text` and `children`.
+/// The first line isn't indented, so it can follow a `- ` list marker.
+/// The children wait on a stack together with the list marker that goes in front of them.
+fn yaml_element(tree: &ElementTree, index: usize, depth: usize, res: &mut String) {
+    let mut stack = vec![(index, depth, String::new())];
+    while let Some((index, depth, marker)) = stack.pop() {
+        let element = &tree.elements[index];
+        let indent = "  ".repeat(depth + 1);
+        res.push_str(&marker);
+        res.push_str(&quote(element.name));
+        res.push(':');
+        if element.attributes.is_empty() && !element.text && element.children.is_empty() {
+            res.push_str(" {}\n");
+            continue;
+        }
+        res.push('\n');
+
+        if !element.attributes.is_empty() {
+            res.push_str(&format!("{}attrs:\n", indent));
+            for key in element.attributes {
+                res.push_str(&format!("{}  - {}\n", indent, quote(key)));
+            }
+        }
+        if element.text {
+            res.push_str(&format!("{}text: true\n", indent));
+        }
+        if !element.children.is_empty() {
+            res.push_str(&format!("{}children:\n", indent));
+            let marker = format!("{}  - ", indent);
+            stack.extend(
+                element
+                    .children
+                    .iter()
+                    .rev()
+                    .map(|child| (*child, depth + 3, marker.clone())),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::quote;
+    use crate::converter::config::Config;
+    use crate::converter::parser::{lex_graph, lex_tokens};
+
+    #[test]
+    fn test_to_yaml() {
+        let tokens = lex_tokens(
+            "<catalog><book id=\"1\" xml:lang=\"en\"><title>Text</title><cover/></book></catalog>",
+        )
+        .unwrap();
+        let graph = lex_graph(tokens, &Config::default()).unwrap();
+
+        assert_eq!(
+            graph.to_yaml(),
+            "catalog:\n\
+            \x20 children:\n\
+            \x20   - book:\n\
+            \x20       attrs:\n\
+            \x20         - id\n\
+            \x20         - xml:lang\n\
+            \x20       children:\n\
+            \x20         - title:\n\
+            \x20             text: true\n\
+            \x20         - cover: {}"
+        );
+    }
+
+    #[test]
+    fn test_to_yaml_roots() {
+        let graph = lex_graph(lex_tokens("").unwrap(), &Config::default()).unwrap();
+        assert_eq!(graph.to_yaml(), "[]");
+
+        let graph = lex_graph(lex_tokens("<a x=\"1\"/><b/>").unwrap(), &Config::default()).unwrap();
+        assert_eq!(graph.to_yaml(), "- a:\n    attrs:\n      - x\n- b: {}");
+    }
+
+    #[test]
+    fn test_quote() {
+        assert_eq!(quote("book"), "book");
+        assert_eq!(quote("soap:Envelope"), "soap:Envelope");
+        assert_eq!(quote("true"), "\"true\"");
+        assert_eq!(quote("12"), "\"12\"");
+        assert_eq!(quote("-a"), "\"-a\"");
+        assert_eq!(quote("a: \"b\""), "\"a: \\\"b\\\"\"");
+    }
+}
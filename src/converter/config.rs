@@ -0,0 +1,285 @@
+/// How `Graph::minimize` turns a group of matching siblings into one node.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MinimizeStrategy {
+    /// Keep the instance with the most children.
+    #[default]
+    MostChildren,
+    /// Keep the first instance in the document.
+    First,
+    /// Combine the attributes and children of every instance, siblings with the same name match regardless of
+    /// their attributes.
+    Merge,
+}
+
+/// How much the conversion tells about what it's doing on stderr.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    /// Nothing is logged.
+    #[default]
+    Off,
+    /// The number of tokens and elements after every step.
+    Info,
+    /// Also every element that is created and every group of siblings that minimize collapses or keeps.
+    Debug,
+}
+
+/// Settings that change how the overview is built.
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    /// Annotates elements with how many times they occur per parent, like `?`, `*` or `+`, and with whether
+    /// only some of their instances are empty.
+    pub cardinality: bool,
+    /// Groups of matching siblings with fewer instances than this are kept as they are.
+    pub collapse_threshold: usize,
+    /// Prints how many times an element occurs in the document after it, like `(×37)`.
+    pub counts: bool,
+    /// The maximum depth of the tree that is printed, deeper elements are replaced by `<name .../>`.
+    pub depth: Option<usize>,
+    /// The maximum number of child elements that is printed per element, the rest are replaced by a comment that
+    /// tells how many were left out.
+    pub max_children: Option<usize>,
+    /// Prints runs of whitespace as a single space, or as a single newline when the run contains one.
+    pub normalize_whitespace: bool,
+    /// Prints text as it appears in the document, instead of decoding entity references like `&#169;`.
+    pub raw_text: bool,
+    /// Marks elements that have both child elements and text with a comment.
+    pub show_mixed: bool,
+    /// Leaves the `xmlns` and `xmlns:prefix` declarations out of the attributes, the overview lists them once
+    /// at the top instead.
+    pub hide_namespaces: bool,
+    /// Prints attributes that only some merged instances have without the `?` that marks them as optional.
+    pub plain_keys: bool,
+    /// Leaves out all text and CDATA, elements that only contain text are printed as `<name/>`.
+    pub no_text: bool,
+    /// Prints the attributes of every element sorted by name, instead of in the order they appear in the document.
+    pub sort_attributes: bool,
+    /// Prints attributes as `key=""` instead of with a sample value.
+    pub strip_values: bool,
+    /// Prints the inferred type of attribute values and of the text of elements without child elements, like
+    /// `{int}`, instead of a sample value.
+    pub types: bool,
+    /// Prints every element on its own line, indented by this many spaces per level instead of the whitespace of
+    /// the document. `0` keeps the whitespace of the document.
+    pub indent: usize,
+    /// Colors the names of elements and attributes and the text with ANSI escape codes, for a terminal.
+    pub color: bool,
+    /// Adds comments to the overview with extra information about the original XML, and keeps its comments.
+    pub verbose: bool,
+    /// Fails on a closing tag that doesn't match the open element, instead of closing the elements inside the
+    /// matching one or ignoring a closing tag that matches none.
+    pub strict: bool,
+    /// Slash-separated paths like `catalog/book/price`, when there are any only the elements at these paths are
+    /// kept, together with their subtrees and the ancestors that lead to them.
+    pub only: Vec<String>,
+    /// Skips collapsing matching siblings, so every element in the document is printed.
+    pub no_minimize: bool,
+    /// Longer attribute values and text are cut off with an ellipsis, `0` means unlimited.
+    pub max_value_len: usize,
+    /// How groups of matching siblings are collapsed.
+    pub minimize_strategy: MinimizeStrategy,
+    /// Elements that never have content or a closing tag, like `<br>` in HTML.
+    pub void_elements: Vec<String>,
+    /// Elements that contain code, like `<script>` and `<style>`. Their text and CDATA are printed as `…`, the code
+    /// says nothing about the structure of the document.
+    pub code_elements: Vec<String>,
+    /// How much is logged to stderr while converting, the overview itself is never affected.
+    pub log_level: LogLevel,
+}
+
+impl Config {
+    /// Starts building a config from the defaults.
+    pub fn builder() -> ConfigBuilder {
+        ConfigBuilder::default()
+    }
+
+    /// Defaults for SVG documents. Editors add namespaced attributes of their own, like `inkscape:label`, and path
+    /// data can be kilobytes long, so the namespaces are listed once and values are cut off early. The code in
+    /// `<script>` and `<style>` is left out.
+    pub fn svg() -> Config {
+        Config {
+            hide_namespaces: true,
+            max_value_len: 20,
+            code_elements: vec![String::from("script"), String::from("style")],
+            ..Config::default()
+        }
+    }
+
+    /// Defaults for XHTML documents, lenient towards HTML void elements that are not self-closed. Every document
+    /// declares the XHTML namespace, so the namespaces are listed once, long values like inline styles and URLs are
+    /// cut off, and the code in `<script>` and `<style>` is left out.
+    pub fn xhtml() -> Config {
+        let void_elements = [
+            "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param",
+            "source", "track", "wbr",
+        ];
+
+        Config {
+            hide_namespaces: true,
+            max_value_len: 40,
+            void_elements: void_elements.map(String::from).to_vec(),
+            code_elements: vec![String::from("script"), String::from("style")],
+            ..Config::default()
+        }
+    }
+
+    pub fn is_void_element(&self, name: &str) -> bool {
+        self.void_elements.iter().any(|element| element == name)
+    }
+
+    pub fn is_code_element(&self, name: &str) -> bool {
+        self.code_elements.iter().any(|element| element == name)
+    }
+
+    /// Writes the message to stderr when the log level is at least `level`.
+    /// The message is only built when it's written, so debug messages cost nothing when they're off.
+    pub fn log(&self, level: LogLevel, message: impl FnOnce() -> String) {
+        if level != LogLevel::Off && self.log_level >= level {
+            eprintln!("[{}] {}", format!("{:?}", level).to_lowercase(), message());
+        }
+    }
+}
+
+/// Builds a [`Config`] one setting at a time, starting from the defaults or from a preset like [`Config::svg`].
+/// Every setting has a setter named after its field, except `minimize_strategy` which is set by `strategy`.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigBuilder {
+    config: Config,
+}
+
+impl ConfigBuilder {
+    pub fn cardinality(mut self, cardinality: bool) -> ConfigBuilder {
+        self.config.cardinality = cardinality;
+        self
+    }
+
+    pub fn collapse_threshold(mut self, collapse_threshold: usize) -> ConfigBuilder {
+        self.config.collapse_threshold = collapse_threshold;
+        self
+    }
+
+    pub fn counts(mut self, counts: bool) -> ConfigBuilder {
+        self.config.counts = counts;
+        self
+    }
+
+    pub fn depth(mut self, depth: impl Into<Option<usize>>) -> ConfigBuilder {
+        self.config.depth = depth.into();
+        self
+    }
+
+    pub fn max_children(mut self, max_children: impl Into<Option<usize>>) -> ConfigBuilder {
+        self.config.max_children = max_children.into();
+        self
+    }
+
+    pub fn normalize_whitespace(mut self, normalize_whitespace: bool) -> ConfigBuilder {
+        self.config.normalize_whitespace = normalize_whitespace;
+        self
+    }
+
+    pub fn raw_text(mut self, raw_text: bool) -> ConfigBuilder {
+        self.config.raw_text = raw_text;
+        self
+    }
+
+    pub fn show_mixed(mut self, show_mixed: bool) -> ConfigBuilder {
+        self.config.show_mixed = show_mixed;
+        self
+    }
+
+    pub fn hide_namespaces(mut self, hide_namespaces: bool) -> ConfigBuilder {
+        self.config.hide_namespaces = hide_namespaces;
+        self
+    }
+
+    pub fn plain_keys(mut self, plain_keys: bool) -> ConfigBuilder {
+        self.config.plain_keys = plain_keys;
+        self
+    }
+
+    pub fn no_text(mut self, no_text: bool) -> ConfigBuilder {
+        self.config.no_text = no_text;
+        self
+    }
+
+    pub fn sort_attributes(mut self, sort_attributes: bool) -> ConfigBuilder {
+        self.config.sort_attributes = sort_attributes;
+        self
+    }
+
+    pub fn strip_values(mut self, strip_values: bool) -> ConfigBuilder {
+        self.config.strip_values = strip_values;
+        self
+    }
+
+    pub fn types(mut self, types: bool) -> ConfigBuilder {
+        self.config.types = types;
+        self
+    }
+
+    pub fn indent(mut self, indent: usize) -> ConfigBuilder {
+        self.config.indent = indent;
+        self
+    }
+
+    pub fn color(mut self, color: bool) -> ConfigBuilder {
+        self.config.color = color;
+        self
+    }
+
+    pub fn verbose(mut self, verbose: bool) -> ConfigBuilder {
+        self.config.verbose = verbose;
+        self
+    }
+
+    pub fn strict(mut self, strict: bool) -> ConfigBuilder {
+        self.config.strict = strict;
+        self
+    }
+
+    pub fn only(mut self, only: Vec<String>) -> ConfigBuilder {
+        self.config.only = only;
+        self
+    }
+
+    pub fn no_minimize(mut self, no_minimize: bool) -> ConfigBuilder {
+        self.config.no_minimize = no_minimize;
+        self
+    }
+
+    pub fn max_value_len(mut self, max_value_len: usize) -> ConfigBuilder {
+        self.config.max_value_len = max_value_len;
+        self
+    }
+
+    /// Sets the [`Config::minimize_strategy`].
+    pub fn strategy(mut self, minimize_strategy: MinimizeStrategy) -> ConfigBuilder {
+        self.config.minimize_strategy = minimize_strategy;
+        self
+    }
+
+    pub fn void_elements(mut self, void_elements: Vec<String>) -> ConfigBuilder {
+        self.config.void_elements = void_elements;
+        self
+    }
+
+    pub fn code_elements(mut self, code_elements: Vec<String>) -> ConfigBuilder {
+        self.config.code_elements = code_elements;
+        self
+    }
+
+    pub fn log_level(mut self, log_level: LogLevel) -> ConfigBuilder {
+        self.config.log_level = log_level;
+        self
+    }
+
+    pub fn build(self) -> Config {
+        self.config
+    }
+}
+
+impl From<Config> for ConfigBuilder {
+    fn from(config: Config) -> ConfigBuilder {
+        ConfigBuilder { config }
+    }
+}
@@ -0,0 +1,206 @@
+use std::io::{self, Read};
+
+use encoding_rs::{Decoder, DecoderResult, Encoding, UTF_8, UTF_16BE, UTF_16LE};
+
+use crate::converter::parser::XmlDeclaration;
+
+/// Picks the encoding of a document from its byte order mark, the layout of its first `<?`, or the `encoding`
+/// in its XML declaration, in that order. Returns the encoding and the length of the byte order mark.
+fn detect(bytes: &[u8]) -> (&'static Encoding, usize) {
+    if let Some(detected) = Encoding::for_bom(bytes) {
+        return detected;
+    }
+
+    // Without a byte order mark, UTF-16 shows itself by the zero bytes around the ASCII of `<?`.
+    if bytes.starts_with(&[b'<', 0, b'?', 0]) {
+        return (UTF_16LE, 0);
+    }
+    if bytes.starts_with(&[0, b'<', 0, b'?']) {
+        return (UTF_16BE, 0);
+    }
+
+    // The declaration is ASCII in every encoding that doesn't need the checks above.
+    let encoding = bytes
+        .strip_prefix(b"<?xml")
+        .and_then(|rest| {
+            let end = rest.windows(2).position(|window| window == b"?>")?;
+            XmlDeclaration::parse(&String::from_utf8_lossy(&rest[..end]))?.encoding
+        })
+        .and_then(|label| Encoding::for_label(label.as_bytes()))
+        // A declaration that claims UTF-16 in a document without zero bytes has already been transcoded.
+        .filter(|encoding| *encoding != UTF_16LE && *encoding != UTF_16BE);
+
+    (encoding.unwrap_or(UTF_8), 0)
+}
+
+/// Decodes a document into a string, transcoding it to UTF-8 when it's in another encoding.
+/// Documents without a byte order mark or declared encoding are read as UTF-8.
+pub fn decode(bytes: &[u8]) -> io::Result<String> {
+    let (encoding, bom_len) = detect(bytes);
+
+    encoding
+        .decode_without_bom_handling_and_without_replacement(&bytes[bom_len..])
+        .map(String::from)
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("the document isn't valid {}", encoding.name()),
+            )
+        })
+}
+
+/// The smallest number of bytes in the first chunk of a document that is decoded in chunks, enough to hold the
+/// XML declaration that the encoding is detected from.
+const DETECTION_LENGTH: usize = 1024;
+
+/// Decodes a document while it's read, one chunk of about `chunk_size` bytes at a time, like [`decode`] does for
+/// a whole document. The encoding is detected from the first chunk, characters that are split between chunks are
+/// completed by the next one.
+pub struct DecodedChunks<R> {
+    reader: R,
+    chunk_size: usize,
+    /// `None` until the first chunk is read and the encoding is known.
+    decoder: Option<Decoder>,
+    done: bool,
+}
+
+impl<R: Read> DecodedChunks<R> {
+    pub fn new(reader: R, chunk_size: usize) -> DecodedChunks<R> {
+        DecodedChunks {
+            reader,
+            chunk_size: chunk_size.max(1),
+            decoder: None,
+            done: false,
+        }
+    }
+
+    /// Reads up to `len` bytes, fewer only at the end of the document.
+    fn read_chunk(&mut self, len: usize) -> io::Result<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(len);
+        (&mut self.reader)
+            .take(len as u64)
+            .read_to_end(&mut bytes)?;
+
+        Ok(bytes)
+    }
+}
+
+impl<R: Read> Iterator for DecodedChunks<R> {
+    type Item = io::Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let len = match self.decoder {
+            Some(_) => self.chunk_size,
+            None => self.chunk_size.max(DETECTION_LENGTH),
+        };
+        let bytes = match self.read_chunk(len) {
+            Ok(bytes) => bytes,
+            Err(err) => {
+                self.done = true;
+                return Some(Err(err));
+            }
+        };
+        // Only the first chunk can start with a byte order mark.
+        let mut bom_len = 0;
+        if self.decoder.is_none() {
+            let (encoding, len) = detect(&bytes);
+            self.decoder = Some(encoding.new_decoder_without_bom_handling());
+            bom_len = len;
+        }
+        let decoder = self
+            .decoder
+            .as_mut()
+            .expect("The decoder is created for the first chunk");
+        let last = bytes.len() < len;
+        self.done = last;
+
+        let mut chunk = String::new();
+        if let Some(capacity) = decoder.max_utf8_buffer_length_without_replacement(bytes.len()) {
+            chunk.reserve(capacity);
+        }
+        match decoder.decode_to_string_without_replacement(&bytes[bom_len..], &mut chunk, last) {
+            (DecoderResult::InputEmpty, _) => Some(Ok(chunk)),
+            _ => {
+                self.done = true;
+                Some(Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("the document isn't valid {}", decoder.encoding().name()),
+                )))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DETECTION_LENGTH, DecodedChunks, decode};
+
+    fn utf16(text: &str, little_endian: bool) -> Vec<u8> {
+        text.encode_utf16()
+            .flat_map(|unit| {
+                if little_endian {
+                    unit.to_le_bytes()
+                } else {
+                    unit.to_be_bytes()
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_decode_utf8() {
+        assert_eq!(decode("<a>é</a>".as_bytes()).unwrap(), "<a>é</a>");
+        assert_eq!(decode("\u{feff}<a>é</a>".as_bytes()).unwrap(), "<a>é</a>");
+        assert!(decode(b"<a>\xff</a>").is_err());
+    }
+
+    #[test]
+    fn test_decode_utf16() {
+        let xml = "<?xml version=\"1.0\" encoding=\"UTF-16\"?><a>é</a>";
+        for little_endian in [true, false] {
+            let bytes = utf16(xml, little_endian);
+            assert_eq!(decode(&bytes).unwrap(), xml);
+
+            let bom = utf16("\u{feff}", little_endian);
+            assert_eq!(decode(&[bom, bytes].concat()).unwrap(), xml);
+        }
+    }
+
+    #[test]
+    fn test_decoded_chunks() {
+        let xml = "<?xml version=\"1.0\" encoding=\"UTF-16\"?><a>é€</a>";
+        let bytes = [utf16("\u{feff}", false), utf16(xml, false)].concat();
+        for chunk_size in [1, 3, 7, bytes.len()] {
+            let chunks: Vec<String> = DecodedChunks::new(bytes.as_slice(), chunk_size)
+                .collect::<Result<_, _>>()
+                .unwrap();
+            assert_eq!(chunks.concat(), xml, "chunk size {}", chunk_size);
+        }
+
+        // The first chunk ends in the middle of the `é`, the next chunk gets all of it.
+        let xml = format!("<a>{}é</a>", "x".repeat(DETECTION_LENGTH - 4));
+        let chunks: Vec<String> = DecodedChunks::new(xml.as_bytes(), 4)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(chunks[0].len(), DETECTION_LENGTH - 1);
+        assert_eq!(chunks[1], "é</a");
+        assert_eq!(chunks.concat(), xml);
+        assert!(DecodedChunks::new(b"<a>\xff</a>".as_slice(), 2).any(|chunk| chunk.is_err()));
+    }
+
+    #[test]
+    fn test_decode_declared_encoding() {
+        let mut bytes = b"<?xml version=\"1.0\" encoding=\"ISO-8859-1\"?><a>".to_vec();
+        bytes.extend([0xe9, 0xfc]);
+        bytes.extend(b"</a>");
+
+        assert_eq!(
+            decode(&bytes).unwrap(),
+            "<?xml version=\"1.0\" encoding=\"ISO-8859-1\"?><a>éü</a>"
+        );
+    }
+}
@@ -0,0 +1,70 @@
+use std::fmt;
+
+use crate::converter::parser::{GraphError, LexError};
+
+#[derive(Debug)]
+pub enum ConvertError {
+    /// The input file couldn't be read.
+    Io(std::io::Error),
+    /// The input file couldn't be split into tokens.
+    Lex(LexError),
+    /// The tokens couldn't be built into a graph.
+    Graph(GraphError),
+    /// No element in the document has the requested name.
+    ElementNotFound(String),
+    /// The document couldn't be downloaded.
+    #[cfg(feature = "http")]
+    Http(ureq::Error),
+    /// The graph couldn't be encoded as MessagePack.
+    #[cfg(feature = "serde")]
+    Encode(rmp_serde::encode::Error),
+}
+
+impl fmt::Display for ConvertError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConvertError::Io(err) => write!(f, "Failed to read file: {}", err),
+            ConvertError::Lex(err) => write!(f, "Failed to lex file: {}", err),
+            ConvertError::Graph(err) => write!(f, "Failed to build graph: {}", err),
+            ConvertError::ElementNotFound(name) => {
+                write!(f, "No matching elements named <{}> in the document", name)
+            }
+            #[cfg(feature = "http")]
+            ConvertError::Http(err) => write!(f, "Failed to download document: {}", err),
+            #[cfg(feature = "serde")]
+            ConvertError::Encode(err) => write!(f, "Failed to encode graph: {}", err),
+        }
+    }
+}
+
+impl From<std::io::Error> for ConvertError {
+    fn from(err: std::io::Error) -> Self {
+        ConvertError::Io(err)
+    }
+}
+
+impl From<LexError> for ConvertError {
+    fn from(err: LexError) -> Self {
+        ConvertError::Lex(err)
+    }
+}
+
+impl From<GraphError> for ConvertError {
+    fn from(err: GraphError) -> Self {
+        ConvertError::Graph(err)
+    }
+}
+
+#[cfg(feature = "http")]
+impl From<ureq::Error> for ConvertError {
+    fn from(err: ureq::Error) -> Self {
+        ConvertError::Http(err)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<rmp_serde::encode::Error> for ConvertError {
+    fn from(err: rmp_serde::encode::Error) -> Self {
+        ConvertError::Encode(err)
+    }
+}
@@ -0,0 +1,50 @@
+use std::time::Duration;
+
+use crate::converter::encoding::decode;
+use crate::converter::errors::ConvertError;
+
+/// Whether the path is an `http://` or `https://` URL instead of a file.
+pub fn is_url(path: &str) -> bool {
+    path.starts_with("http://") || path.starts_with("https://")
+}
+
+/// Downloads the document at the URL and decodes it like [`decode`] does for a file.
+/// Without a timeout the request can wait forever, with one the whole request has to finish in time.
+pub fn fetch(url: &str, timeout: Option<Duration>) -> Result<String, ConvertError> {
+    let agent: ureq::Agent = ureq::Agent::config_builder()
+        .timeout_global(timeout)
+        .build()
+        .into();
+    let bytes = agent
+        .get(url)
+        .call()?
+        .body_mut()
+        .with_config()
+        .limit(u64::MAX)
+        .read_to_vec()?;
+
+    Ok(decode(&bytes)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fetch, is_url};
+    use crate::converter::errors::ConvertError;
+
+    #[test]
+    fn test_is_url() {
+        assert!(is_url("https://example.com/feed.xml"));
+        assert!(is_url("http://localhost:8080/"));
+        assert!(!is_url("feed.xml"));
+        assert!(!is_url("./http://feed.xml"));
+    }
+
+    #[test]
+    fn test_fetch_error() {
+        // Nothing listens on port 1, so the connection is refused without leaving the machine.
+        assert!(matches!(
+            fetch("http://127.0.0.1:1/feed.xml", None),
+            Err(ConvertError::Http(_))
+        ));
+    }
+}
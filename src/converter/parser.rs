@@ -1,329 +1,553 @@
+mod declaration;
+mod entities;
 mod errors;
+mod lexer;
 mod token;
 
-use errors::LexError;
-use token::Token;
-
-type LexResult = (Token, String);
-
-fn lex_tag_open(file: String, tag: &str) -> Option<(String, String)> {
-    if !file.starts_with(tag) {
-        return None;
-    }
-
-    let offset = tag.len();
-
-    let mut file_remainder = file.clone();
-
-    // If any of these lexers return a token, then we've reached the end of the tag name.
-    let closing_lexers = [
-        lex_tag_self_closing,
-        lex_tag_closing,
-        lex_comment,
-        lex_whitespace,
-    ];
-
-    let mut str_body_len = 0;
-    while file_remainder.len() > 0 {
-        if closing_lexers
-            .iter()
-            .any(|lexer| lexer(file_remainder.clone()).is_some())
-        {
-            break;
+use crate::converter::block::Graph;
+use crate::converter::config::{Config, LogLevel};
+pub use declaration::XmlDeclaration;
+pub use entities::{decode_entities, escape_attribute, escape_text};
+pub use errors::{GraphError, LexError};
+pub use lexer::{ChunkedTokens, Tokens, lex_spanned_tokens, lex_tokens};
+pub use token::{SpannedToken, Token};
+
+use lexer::lex_attribute_tokens;
+
+/// Splits a qualified name like `soap:Envelope` into its namespace prefix and local part.
+pub fn split_qualified_name(name: &str) -> (Option<&str>, &str) {
+    match name.split_once(':') {
+        Some((prefix, local_name)) if !prefix.is_empty() && !local_name.is_empty() => {
+            (Some(prefix), local_name)
         }
-
-        str_body_len += 1;
-        file_remainder = String::from(&file_remainder[1..]);
+        _ => (None, name),
     }
+}
 
-    return Some((
-        String::from(&file[offset..str_body_len]),
-        String::from(&file[str_body_len..]),
-    ));
+/// Builds the element graph from a stream of tokens.
+/// Every token that isn't part of a tag is stored as a child of the element it appears in.
+/// The spans of the elements come from the spans of their tags, they are empty for tokens without a span.
+pub fn lex_graph(
+    tokens: impl IntoIterator<Item = impl Into<SpannedToken>>,
+    config: &Config,
+) -> Result<Graph, GraphError> {
+    let mut graph = Graph::new();
+    lex_graph_into(&mut graph, tokens, config)?;
+
+    Ok(graph)
 }
 
-fn lex_tag_open_start(file: String) -> Option<LexResult> {
-    if let Some((name, remainder)) = lex_tag_open(file, "<") {
-        return Some((Token::TagOpenStart(name), remainder));
-    }
+/// Adds the elements of another document to the graph, as extra children of the synthetic root.
+/// The XML declaration of the graph is only set by the first document that has one.
+pub fn lex_graph_into(
+    graph: &mut Graph,
+    tokens: impl IntoIterator<Item = impl Into<SpannedToken>>,
+    config: &Config,
+) -> Result<(), GraphError> {
+    let had_declaration = graph.xml_declaration().is_some();
+    let mut tokens = tokens.into_iter().map(Into::into);
+    // Where the last token ended, elements that are never closed end there.
+    let mut end = 0;
+
+    // A comment that is only followed by whitespace documents the next element.
+    let mut description = None;
+
+    while let Some((token, span)) = tokens.next() {
+        end = span.end;
+        match &token {
+            Token::Comment(comment) => {
+                // XML doesn't allow `--` in a comment, which also rules out a comment that ends in `--->`.
+                if comment.contains("--") || comment.ends_with('-') {
+                    graph.warn(format!(
+                        "Comment <!--{}--> contains '--', which XML doesn't allow",
+                        comment
+                    ));
+                }
+                description = Some(comment.trim().to_string())
+            }
+            Token::Whitespace | Token::Newline | Token::TagOpenStart(_) => (),
+            _ => description = None,
+        }
 
-    None
-}
+        match token {
+            Token::TagOpenStart(name) => {
+                let mut keys_inside_tag = Vec::<String>::new();
+                let mut values_inside_tag = Vec::<String>::new();
+                let mut self_closing = None;
+                // The value of a repeated or hidden key is dropped together with the key.
+                let mut skip_value = false;
+                // A hidden namespace declaration is kept by the graph once its value is known.
+                let mut namespace = None;
+                for (token, span) in tokens.by_ref() {
+                    end = span.end;
+                    match token {
+                        Token::Key(key) if keys_inside_tag.contains(&key) => {
+                            graph.warn(format!(
+                                "<{}> has a duplicate attribute {}, ignoring all but the first",
+                                name, key
+                            ));
+                            namespace = None;
+                            skip_value = true;
+                        }
+                        Token::Key(key)
+                            if config.hide_namespaces
+                                && (key == "xmlns" || key.starts_with("xmlns:")) =>
+                        {
+                            namespace = Some(key);
+                            skip_value = true;
+                        }
+                        Token::Key(key) => {
+                            keys_inside_tag.push(key);
+                            values_inside_tag.push(String::new());
+                            namespace = None;
+                            skip_value = false;
+                        }
+                        Token::String(value) if skip_value => {
+                            if let Some(key) = namespace.take() {
+                                graph.add_namespace(key, decode_entities(&value));
+                            }
+                            skip_value = false;
+                        }
+                        // Values are kept decoded, printing escapes them again.
+                        Token::String(value) => {
+                            if let Some(last) = values_inside_tag.last_mut() {
+                                *last = decode_entities(&value);
+                            }
+                        }
+                        Token::TagClosing => {
+                            self_closing = Some(false);
+                            break;
+                        }
+                        Token::TagSelfClosing => {
+                            self_closing = Some(true);
+                            break;
+                        }
+                        _ => (),
+                    }
+                }
+
+                let Some(self_closing) = self_closing else {
+                    return Err(GraphError::UnclosedTag { name });
+                };
+
+                let void_element = config.is_void_element(&name);
+                let parent = graph.current_node().id;
+                let id = graph.add_node(name, keys_inside_tag, values_inside_tag);
+                config.log(LogLevel::Debug, || {
+                    format!(
+                        "Created <{}> as node {} under node {}",
+                        graph.get_node(id).name,
+                        id,
+                        parent
+                    )
+                });
+                if let Some(description) = description.take() {
+                    graph.set_description(id, description);
+                }
+                graph.set_span(id, span.start..end);
+                if self_closing || void_element {
+                    graph.set_self_closing(id);
+                    graph.close_current(end);
+                }
+            }
+            Token::TagCloseStart(name) => {
+                let Some((_, closing)) = tokens.find(|(token, _)| *token == Token::TagClosing)
+                else {
+                    return Err(GraphError::UnclosedClosingTag { name });
+                };
+                end = closing.end;
+
+                // Void elements are already closed by their opening tag.
+                if !config.is_void_element(&name) {
+                    close_element(graph, &name, end, config)?;
+                }
+            }
+            Token::XmlDeclaration(data) => {
+                let declaration = XmlDeclaration::parse(&data);
+                if declaration.is_none() {
+                    graph.warn(format!("Ignoring invalid XML declaration <?xml{}?>", data));
+                }
+                if !had_declaration {
+                    graph.set_xml_declaration(declaration);
+                }
+            }
+            // The lexer only yields these inside a tag, so they can't follow anything but a tag name.
+            token @ (Token::Key(_)
+            | Token::String(_)
+            | Token::TagClosing
+            | Token::TagSelfClosing) => return Err(GraphError::UnexpectedToken { token }),
+            token => graph.add_token(token),
+        }
+    }
 
-fn lex_tag_close_start(file: String) -> Option<LexResult> {
-    if let Some((name, remainder)) = lex_tag_open(file, "</") {
-        return Some((Token::TagCloseStart(name), remainder));
+    while graph.current_node().parent.is_some() {
+        let name = graph.current_node().name.clone();
+        graph.warn(format!("<{}> is never closed", name));
+        graph.close_current(end);
     }
 
-    None
+    Ok(())
 }
 
-fn lex_comment(file: String) -> Option<LexResult> {
-    let comment_closing_tag = "-->";
-    let comment_closing_tag_len = comment_closing_tag.len();
-
-    let comment_opening_tag = "<!--";
-    let comment_opening_tag_len = comment_opening_tag.len();
-
-    if file.starts_with(comment_opening_tag) {
-        let comment_end = file.find(comment_closing_tag);
-        if let Some(index) = comment_end {
-            return Some((
-                Token::Comment(String::from(&file[comment_opening_tag_len..index])),
-                String::from(&file[index + comment_closing_tag_len..]),
-            ));
-        }
+/// Closes the open element with this name, its span ends at `end`. Unless the config is strict, a closing tag
+/// that doesn't match the current element is recovered from with a warning: the elements that are open inside
+/// the matching one are closed with it, and a closing tag without any matching open element is ignored.
+fn close_element(
+    graph: &mut Graph,
+    name: &str,
+    end: usize,
+    config: &Config,
+) -> Result<(), GraphError> {
+    let current = graph.current_node();
+    if current.parent.is_some() && current.name == name {
+        graph.close_current(end);
+        return Ok(());
     }
 
-    None
-}
+    let mut ancestor = current;
+    let mut open = Vec::<String>::new();
+    while ancestor.parent.is_some() && ancestor.name != name {
+        open.push(ancestor.name.clone());
+        ancestor = graph.get_node(ancestor.parent.unwrap_or_default());
+    }
+    let matched = ancestor.parent.is_some();
 
-fn lex_string(file: String) -> Option<LexResult> {
-    let string_closing_tag = "\"";
-    let offset = 1;
-    if file.starts_with("\"") {
-        let string_end = file[offset..].find(string_closing_tag);
-        if let Some(end_pos) = string_end {
-            let index = end_pos + offset;
-            return Some((
-                Token::String(String::from(&file[1..index])),
-                String::from(&file[index + 1..]),
-            ));
-        }
+    if config.strict {
+        return Err(GraphError::MismatchedClosingTag {
+            name: String::from(name),
+            open: open.into_iter().next(),
+        });
     }
 
-    None
-}
+    if !matched {
+        graph.warn(format!(
+            "Ignoring closing tag </{}> without a matching open element",
+            name
+        ));
+        return Ok(());
+    }
 
-fn lex_key(file: String) -> Option<LexResult> {
-    if let Some(c) = file.chars().next() {
-        if !c.is_alphabetic() {
-            return None;
-        }
-        let string_end = file[1..].find('=');
-        if let Some(end_pos) = string_end {
-            let index = end_pos + 1;
-            return Some((
-                Token::Key(String::from(&file[0..index])),
-                String::from(&file[index + 1..]),
-            ));
-        }
+    for open_name in open {
+        graph.warn(format!(
+            "<{}> is implicitly closed by </{}>",
+            open_name, name
+        ));
+        graph.close_current(end);
     }
+    graph.close_current(end);
 
-    None
+    Ok(())
 }
 
-fn lex_tag_self_closing(file: String) -> Option<LexResult> {
-    if file.starts_with("/>") {
-        return Some((Token::TagSelfClosing, String::from(&file[2..])));
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_qualified_name() {
+        assert_eq!(
+            split_qualified_name("soap:Envelope"),
+            (Some("soap"), "Envelope")
+        );
+        assert_eq!(split_qualified_name("Envelope"), (None, "Envelope"));
+        assert_eq!(split_qualified_name(":Envelope"), (None, ":Envelope"));
     }
 
-    None
-}
+    #[test]
+    fn test_lex_graph_doctype() {
+        let tokens = lex_tokens("<!DOCTYPE x [ <!ENTITY a \"b\"> ]>\n<x><y/></x>").unwrap();
+        let graph = lex_graph(tokens, &Config::default()).unwrap();
 
-fn lex_tag_closing(file: String) -> Option<LexResult> {
-    if file.starts_with(">") {
-        return Some((Token::TagClosing, String::from(&file[1..])));
+        assert!(graph.warnings().is_empty());
+        assert_eq!(graph.print(&Config::default()), "<x><y/></x>");
     }
 
-    None
-}
+    #[test]
+    fn test_lex_graph_warnings() {
+        let graph =
+            lex_graph(lex_tokens("<?xml?><a><b></b>").unwrap(), &Config::default()).unwrap();
 
-fn lex_whitespace(file: String) -> Option<LexResult> {
-    if [' ', '\t', '\n']
-        .map(|c| Some(c))
-        .contains(&file.chars().next())
-    {
-        return Some((Token::Whitespace, String::from(&file[1..])));
+        assert_eq!(
+            graph.warnings(),
+            [
+                String::from("Ignoring invalid XML declaration <?xml?>"),
+                String::from("<a> is never closed"),
+            ]
+        );
     }
 
-    None
-}
+    #[test]
+    fn test_lex_graph_mismatched_closing_tag() {
+        let tokens = lex_tokens("<r><a><b></a></b><c/></r>").unwrap();
+        let graph = lex_graph(tokens, &Config::default()).unwrap();
 
-fn lex_text(file: String) -> Option<LexResult> {
-    let mut file_remainder = file.clone();
-
-    // If any of these lexers return a token, then we've reached the end of this text token.
-    let closing_lexers = [
-        lex_tag_open_start,
-        lex_tag_close_start,
-        lex_tag_self_closing,
-        lex_tag_closing,
-        lex_comment,
-    ];
-
-    let mut txt_body_len = 0;
-    while file_remainder.len() > 0 {
-        if closing_lexers
-            .iter()
-            .any(|lexer| lexer(file_remainder.clone()).is_some())
-        {
-            break;
-        }
+        assert_eq!(
+            graph.warnings(),
+            [
+                String::from("<b> is implicitly closed by </a>"),
+                String::from("Ignoring closing tag </b> without a matching open element"),
+            ]
+        );
+        assert_eq!(graph.print(&Config::default()), "<r><a><b></b></a><c/></r>");
 
-        txt_body_len += 1;
-        file_remainder = String::from(&file_remainder[1..]);
+        let strict = Config {
+            strict: true,
+            ..Config::default()
+        };
+        let tokens = lex_tokens("<r><a><b></a></b></r>").unwrap();
+        assert_eq!(
+            lex_graph(tokens, &strict).unwrap_err(),
+            GraphError::MismatchedClosingTag {
+                name: String::from("a"),
+                open: Some(String::from("b")),
+            }
+        );
     }
 
-    return Some((
-        Token::Text(String::from(&file[0..txt_body_len])),
-        String::from(&file[txt_body_len..]),
-    ));
-}
-fn lex_token(file: String) -> Result<LexResult, LexError> {
-    let lexers = [
-        lex_comment,
-        lex_string,
-        lex_tag_close_start,
-        lex_tag_open_start,
-        lex_tag_self_closing,
-        lex_tag_closing,
-        lex_whitespace,
-        lex_key,
-        lex_text,
-    ];
-
-    for lexer in lexers {
-        if let Some(r) = lexer(file.clone()) {
-            return Ok(r);
-        }
-    }
+    #[test]
+    fn test_lex_graph_errors() {
+        let config = Config::default();
+        let tokens = vec![Token::TagOpenStart(String::from("a")), Token::Whitespace];
+        let err = lex_graph(tokens, &config).unwrap_err();
+        assert_eq!(
+            err,
+            GraphError::UnclosedTag {
+                name: String::from("a")
+            }
+        );
+        assert_eq!(err.to_string(), "Failed to find a closing tag for <a");
 
-    Err(LexError::UnexpectedString(file))
-}
+        let tokens = lex_tokens("<a></a").unwrap();
+        assert_eq!(
+            lex_graph(tokens, &config).unwrap_err(),
+            GraphError::UnclosedClosingTag {
+                name: String::from("a")
+            }
+        );
 
-fn lex_tokens(file: String) -> Result<Vec<Token>, LexError> {
-    let mut file_to_lex = file;
-    let mut tokens = Vec::<Token>::new();
-    loop {
-        let (token, file_remainder) = lex_token(file_to_lex.clone())?;
-        tokens.push(token);
+        let tokens = vec![Token::Text(String::from("a")), Token::TagClosing];
+        assert_eq!(
+            lex_graph(tokens, &config).unwrap_err(),
+            GraphError::UnexpectedToken {
+                token: Token::TagClosing
+            }
+        );
+    }
 
-        if file_remainder.len() == 0 {
-            break;
+    #[test]
+    fn test_lex_graph_text_with_markup_characters() {
+        let config = Config::default();
+        for xml in [
+            "<p>\"hi\" there</p>",
+            "<p>'x'</p>",
+            "<p>a=b</p>",
+            "<p>1 > 0</p>",
+            "<p>see: a/>b</p>",
+        ] {
+            let graph = lex_graph(lex_tokens(xml).unwrap(), &config).unwrap();
+
+            assert_eq!(graph.print(&config), xml);
         }
-
-        file_to_lex = file_remainder;
     }
 
-    Ok(tokens)
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
     #[test]
-    fn test_lex_next_token_open() {
+    fn test_lex_graph_orphaned_closing_tag() {
+        let tokens = lex_tokens("<a><b/></c></a></a>").unwrap();
+        let graph = lex_graph(tokens, &Config::default()).unwrap();
+
         assert_eq!(
-            lex_token(String::from("<element />")),
-            Ok((
-                Token::TagOpenStart(String::from("element")),
-                String::from(" />")
-            ))
+            graph.warnings(),
+            [
+                String::from("Ignoring closing tag </c> without a matching open element"),
+                String::from("Ignoring closing tag </a> without a matching open element"),
+            ]
         );
+        assert_eq!(graph.print(&Config::default()), "<a><b/></a>");
 
+        let strict = Config {
+            strict: true,
+            ..Config::default()
+        };
+        let tokens = lex_tokens("<a><b/></c></a>").unwrap();
         assert_eq!(
-            lex_token(String::from("<element/>")),
-            Ok((
-                Token::TagOpenStart(String::from("element")),
-                String::from("/>")
-            ))
+            lex_graph(tokens, &strict).unwrap_err(),
+            GraphError::MismatchedClosingTag {
+                name: String::from("c"),
+                open: Some(String::from("a")),
+            }
         );
-
+        let tokens = lex_tokens("<a/></a>").unwrap();
+        let err = lex_graph(tokens, &strict).unwrap_err();
         assert_eq!(
-            lex_token(String::from("</element />")),
-            Ok((
-                Token::TagCloseStart(String::from("element")),
-                String::from(" />")
-            ))
+            err,
+            GraphError::MismatchedClosingTag {
+                name: String::from("a"),
+                open: None,
+            }
         );
-
         assert_eq!(
-            lex_token(String::from("</element<!-- comment --> />")),
-            Ok((
-                Token::TagCloseStart(String::from("element")),
-                String::from("<!-- comment --> />")
-            ))
+            err.to_string(),
+            "Closing tag </a> doesn't match any open element"
         );
     }
 
     #[test]
-    fn test_lex_next_token_selfclosing() {
+    fn test_lex_graph_double_hyphen_comment() {
+        let tokens = lex_tokens("<a><!-- a -- b --><!-- fine - too --><!-- c ---></a>").unwrap();
+        let graph = lex_graph(tokens, &Config::default()).unwrap();
+
         assert_eq!(
-            lex_token(String::from("/> ")),
-            Ok((Token::TagSelfClosing, String::from(" ")))
+            graph.warnings(),
+            [
+                String::from("Comment <!-- a -- b --> contains '--', which XML doesn't allow"),
+                String::from("Comment <!-- c ---> contains '--', which XML doesn't allow"),
+            ]
         );
     }
 
     #[test]
-    fn test_lex_next_token_close() {
+    fn test_lex_graph_duplicate_attributes() {
+        let tokens = lex_tokens("<x id=\"1\" id=\"2\" name=\"a\"/>").unwrap();
+        let graph = lex_graph(tokens, &Config::default()).unwrap();
+
         assert_eq!(
-            lex_token(String::from("><")),
-            Ok((Token::TagClosing, String::from("<")))
+            graph.warnings(),
+            [String::from(
+                "<x> has a duplicate attribute id, ignoring all but the first"
+            )]
         );
+        assert_eq!(graph.print(&Config::default()), "<x id=\"1\" name=\"a\"/>");
     }
 
     #[test]
-    fn test_lex_comment() {
+    fn test_lex_graph_hide_namespaces() {
+        let xml = "<soap:Envelope xmlns:soap=\"http://example.com/soap\" id=\"1\">\
+            <Body xmlns=\"http://example.com/body\"/><Body xmlns=\"http://example.com/body\"/>\
+        </soap:Envelope>";
+        let config = Config {
+            hide_namespaces: true,
+            ..Config::default()
+        };
+        let graph = lex_graph(lex_tokens(xml).unwrap(), &config).unwrap();
+
+        assert_eq!(
+            graph.namespaces(),
+            [
+                (
+                    String::from("xmlns:soap"),
+                    String::from("http://example.com/soap")
+                ),
+                (
+                    String::from("xmlns"),
+                    String::from("http://example.com/body")
+                ),
+            ]
+        );
         assert_eq!(
-            lex_token(String::from("<!-- This is a comment -->")),
-            Ok((
-                Token::Comment(String::from(" This is a comment ")),
-                String::from("")
-            ))
-        )
+            graph.print(&config),
+            "<!-- xmlns:soap=\"http://example.com/soap\" xmlns=\"http://example.com/body\" -->\n\
+            <soap:Envelope id=\"1\"><Body/><Body/></soap:Envelope>"
+        );
     }
 
     #[test]
-    fn test_lex_next_token_string() {
-        assert_eq!(
-            lex_token(String::from("\"string content\" />")),
-            Ok((
-                Token::String(String::from("string content")),
-                String::from(" />")
-            ))
-        );
+    fn test_lex_graph_bare_keys() {
+        let tokens = lex_tokens("<input disabled type=\"checkbox\" required/>").unwrap();
+        let graph = lex_graph(tokens, &Config::default()).unwrap();
+
+        assert_eq!(graph.get_node(1).keys, ["disabled", "type", "required"]);
     }
 
     #[test]
-    fn test_lex_next_token_key() {
+    fn test_lex_graph_whitespace_around_equals() {
+        let tokens = lex_tokens("<a b = \"1\" c =\n'2' d/>").unwrap();
+        let graph = lex_graph(tokens, &Config::default()).unwrap();
+
         assert_eq!(
-            lex_token(String::from("<element />")),
-            Ok((
-                Token::TagOpenStart(String::from("element")),
-                String::from(" />")
-            ))
+            graph.print(&Config::default()),
+            "<a b=\"1\" c=\"2\" d=\"\"/>"
         );
     }
 
     #[test]
-    fn test_lex_all_tokens() {
+    fn test_lex_crlf() {
         assert_eq!(
-            lex_tokens(String::from("</ <!-- comment --> >")),
+            lex_tokens("<a>\r\n<b/>\rx\r\ny</a>"),
             Ok(vec![
-                Token::TagCloseStart(String::new()),
-                Token::Whitespace,
-                Token::Comment(String::from(" comment ")),
-                Token::Whitespace,
-                Token::TagClosing
+                Token::TagOpenStart(String::from("a")),
+                Token::TagClosing,
+                Token::Newline,
+                Token::TagOpenStart(String::from("b")),
+                Token::TagSelfClosing,
+                Token::Newline,
+                Token::Text(String::from("x\ny")),
+                Token::TagCloseStart(String::from("a")),
+                Token::TagClosing,
             ])
         );
+
+        let overview = |xml: &str| {
+            let mut graph = lex_graph(lex_tokens(xml).unwrap(), &Config::default()).unwrap();
+            graph.minimize(&Config::default());
+            graph.print(&Config::default())
+        };
+        assert_eq!(
+            overview("<a>\r\n  <b/>\r\n  <b/>\r\n</a>\r\n"),
+            overview("<a>\n  <b/>\n  <b/>\n</a>\n")
+        );
+    }
+
+    #[test]
+    fn test_lex_graph_void_elements() {
+        let tokens = lex_tokens("<p>a<br>b<br></br>c</p>").unwrap();
+        let graph = lex_graph(tokens, &Config::xhtml()).unwrap();
+
+        assert!(graph.warnings().is_empty());
+        assert_eq!(graph.print(&Config::xhtml()), "<p>a<br/>b<br/>c</p>");
     }
 
     #[test]
-    fn test_lex_all_tokens_from_file() {
-        let file = String::from(
-            "
-        <tag>
-            <child key=\"1\">Content</child>
-            <child>More Content</child>
-            <selfclosingchild/>
-        </tag>
-        ",
+    fn test_lex_graph_descriptions() {
+        let tokens = lex_tokens("<!-- A customer -->\n<customer>\n  <!-- The customer's ID -->\n  <id>1</id>\n  <!-- Not documentation -->text<name/>\n</customer>")
+        .unwrap();
+        let graph = lex_graph(tokens, &Config::default()).unwrap();
+
+        let description_of = |name: &str| {
+            graph
+                .iter()
+                .find(|node| node.name == name)
+                .unwrap()
+                .description
+                .clone()
+        };
+        assert_eq!(description_of("customer"), Some(String::from("A customer")));
+        assert_eq!(
+            description_of("id"),
+            Some(String::from("The customer's ID"))
         );
-        let result = lex_tokens(file);
+        assert_eq!(description_of("name"), None);
+    }
 
-        assert!(result.is_ok());
+    #[test]
+    fn test_lex_graph_spans() {
+        let xml = "<?xml version=\"1.0\"?>\n<r>\n  <a x=\"é\">text</a>\n  <b/>\n  <c><d></c>\n</r>";
+        let graph = lex_graph(lex_spanned_tokens(xml).unwrap(), &Config::default()).unwrap();
+
+        let span_of = |name: &str| {
+            graph
+                .iter()
+                .find(|node| node.name == name)
+                .unwrap()
+                .span
+                .clone()
+        };
+        assert_eq!(&xml[span_of("a")], "<a x=\"é\">text</a>");
+        assert_eq!(&xml[span_of("b")], "<b/>");
+        assert_eq!(&xml[span_of("c")], "<c><d></c>");
+        assert_eq!(&xml[span_of("d")], "<d></c>");
+        assert_eq!(span_of("r"), xml.find("<r>").unwrap()..xml.len());
+
+        let graph = lex_graph(lex_tokens(xml).unwrap(), &Config::default()).unwrap();
+        assert!(graph.iter().all(|node| node.span.is_empty()));
     }
 }
@@ -0,0 +1,114 @@
+#[cfg(feature = "serde")]
+use serde::Serialize;
+use std::fmt;
+
+use super::{Token, lex_attribute_tokens};
+
+/// The pseudo-attributes of an `<?xml ... ?>` declaration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct XmlDeclaration {
+    pub version: String,
+    pub encoding: Option<String>,
+    pub standalone: Option<bool>,
+}
+
+impl XmlDeclaration {
+    /// Parses the data between `<?xml` and `?>`.
+    /// Returns `None` when the mandatory `version` is missing or the data can't be lexed.
+    pub fn parse(data: &str) -> Option<XmlDeclaration> {
+        let tokens = lex_attribute_tokens(data).ok()?;
+
+        let mut version = None;
+        let mut encoding = None;
+        let mut standalone = None;
+
+        let mut key = None;
+        for token in tokens {
+            match token {
+                Token::Key(k) => key = Some(k),
+                Token::String(value) => match key.take().as_deref() {
+                    Some("version") => version = Some(value),
+                    Some("encoding") => encoding = Some(value),
+                    Some("standalone") => {
+                        standalone = match value.as_str() {
+                            "yes" => Some(true),
+                            "no" => Some(false),
+                            _ => None,
+                        }
+                    }
+                    _ => (),
+                },
+                _ => (),
+            }
+        }
+
+        Some(XmlDeclaration {
+            version: version?,
+            encoding,
+            standalone,
+        })
+    }
+}
+
+impl fmt::Display for XmlDeclaration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<?xml version=\"{}\"", self.version)?;
+        if let Some(encoding) = &self.encoding {
+            write!(f, " encoding=\"{}\"", encoding)?;
+        }
+        if let Some(standalone) = self.standalone {
+            write!(
+                f,
+                " standalone=\"{}\"",
+                if standalone { "yes" } else { "no" }
+            )?;
+        }
+        write!(f, "?>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_declaration() {
+        assert_eq!(
+            XmlDeclaration::parse(" version=\"1.1\" encoding=\"UTF-8\" standalone=\"yes\""),
+            Some(XmlDeclaration {
+                version: String::from("1.1"),
+                encoding: Some(String::from("UTF-8")),
+                standalone: Some(true),
+            })
+        );
+
+        assert_eq!(
+            XmlDeclaration::parse(" version=\"1.0\""),
+            Some(XmlDeclaration {
+                version: String::from("1.0"),
+                encoding: None,
+                standalone: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_declaration_without_version() {
+        assert_eq!(XmlDeclaration::parse(" encoding=\"UTF-8\""), None);
+    }
+
+    #[test]
+    fn test_display_declaration() {
+        let declaration = XmlDeclaration {
+            version: String::from("1.0"),
+            encoding: Some(String::from("UTF-8")),
+            standalone: Some(false),
+        };
+
+        assert_eq!(
+            declaration.to_string(),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"no\"?>"
+        );
+    }
+}
@@ -0,0 +1,90 @@
+/// Replaces the predefined entities and numeric character references in text by the characters they stand for.
+/// Anything that isn't a complete, known reference, like a lone `&`, is kept as it is.
+pub fn decode_entities(text: &str) -> String {
+    let mut res = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        res.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest
+            .find(';')
+            .and_then(|end| Some((decode_entity(&rest[1..end])?, end)));
+        match decoded {
+            Some((c, end)) => {
+                res.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                res.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    res.push_str(rest);
+
+    res
+}
+
+/// Escapes the characters that can't appear literally in text, the inverse of [`decode_entities`].
+pub fn escape_text(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;")
+}
+
+/// Escapes the characters that can't appear literally in a double quoted attribute value.
+pub fn escape_attribute(value: &str) -> String {
+    escape_text(value).replace('"', "&quot;")
+}
+
+/// Decodes the name of a single reference, without the `&` and `;`.
+fn decode_entity(name: &str) -> Option<char> {
+    match name {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        _ => {
+            let number = name.strip_prefix('#')?;
+            let code = match number.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => number.parse().ok()?,
+            };
+            char::from_u32(code)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_entities() {
+        assert_eq!(
+            decode_entities("a &amp; b &lt;c&gt; &quot;d&apos;"),
+            "a & b <c> \"d'"
+        );
+        assert_eq!(decode_entities("&#169; &#xA9; &#XE9;"), "© © é");
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape_text("a & b < c > \"d\""), "a &amp; b &lt; c > \"d\"");
+        assert_eq!(
+            escape_attribute("a & b < c \"d\""),
+            "a &amp; b &lt; c &quot;d&quot;"
+        );
+        assert_eq!(
+            escape_text(&decode_entities("&amp;lt; &#169;")),
+            "&amp;lt; ©"
+        );
+    }
+
+    #[test]
+    fn test_decode_entities_keeps_unknown() {
+        assert_eq!(decode_entities("fish & chips"), "fish & chips");
+        assert_eq!(decode_entities("&nbsp; &#xZZ; &amp"), "&nbsp; &#xZZ; &amp");
+        assert_eq!(decode_entities("& &amp;"), "& &");
+    }
+}
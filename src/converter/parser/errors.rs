@@ -1,4 +1,74 @@
+use std::fmt;
+
+use super::Token;
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum LexError {
-    UnexpectedString(String),
+    /// None of the lexers recognized the input at this position.
+    /// The line and column are 1-based, the snippet is the start of the input that couldn't be lexed.
+    UnexpectedString {
+        line: usize,
+        column: usize,
+        snippet: String,
+    },
+    /// A comment starts at this position but there's no `-->` after it.
+    UnterminatedComment { line: usize, column: usize },
+}
+
+impl fmt::Display for LexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LexError::UnexpectedString {
+                line,
+                column,
+                snippet,
+            } => write!(
+                f,
+                "lex error at {}:{}: unexpected '{}'",
+                line, column, snippet
+            ),
+            LexError::UnterminatedComment { line, column } => write!(
+                f,
+                "lex error at {}:{}: comment is never closed with '-->'",
+                line, column
+            ),
+        }
+    }
+}
+
+/// Why a stream of tokens couldn't be built into a graph.
+#[derive(Debug, PartialEq, Eq)]
+pub enum GraphError {
+    /// The opening tag `<name` is never ended with a `>` or `/>`.
+    UnclosedTag { name: String },
+    /// The closing tag `</name` is never ended with a `>`.
+    UnclosedClosingTag { name: String },
+    /// In strict mode, a closing tag that doesn't match the current element. `open` is the name of the current
+    /// element, `None` when no element is open.
+    MismatchedClosingTag { name: String, open: Option<String> },
+    /// A token that only belongs inside a tag, like a key, appeared outside of one.
+    UnexpectedToken { token: Token },
+}
+
+impl fmt::Display for GraphError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GraphError::UnclosedTag { name } => {
+                write!(f, "Failed to find a closing tag for <{}", name)
+            }
+            GraphError::UnclosedClosingTag { name } => {
+                write!(f, "Failed to find a closing tag for </{}", name)
+            }
+            GraphError::MismatchedClosingTag {
+                name,
+                open: Some(open),
+            } => write!(f, "Closing tag </{}> doesn't match <{}>", name, open),
+            GraphError::MismatchedClosingTag { name, open: None } => {
+                write!(f, "Closing tag </{}> doesn't match any open element", name)
+            }
+            GraphError::UnexpectedToken { token } => {
+                write!(f, "Unexpected {:?} outside of a tag", token)
+            }
+        }
+    }
 }
@@ -0,0 +1,1197 @@
+use super::{LexError, SpannedToken, Token};
+
+/// A token together with the number of bytes of the input it was lexed from.
+type LexResult = (Token, usize);
+
+/// Whitespace as XML defines it: space, tab, carriage return and line feed. Other Unicode whitespace, like a
+/// non-breaking space, is an ordinary character.
+fn is_xml_whitespace(c: char) -> bool {
+    matches!(c, ' ' | '\t' | '\r' | '\n')
+}
+
+/// Lexes the name of a tag that starts with `tag`, returning the name and the number of consumed bytes.
+fn lex_tag_open(file: &str, tag: &str) -> Option<(String, usize)> {
+    if !file.starts_with(tag) {
+        return None;
+    }
+
+    let offset = tag.len();
+
+    // If any of these lexers return a token, then we've reached the end of the tag name.
+    let closing_lexers = [
+        lex_tag_self_closing,
+        lex_tag_closing,
+        lex_comment,
+        lex_whitespace,
+        lex_newline,
+    ];
+
+    // The name starts after the prefix, so the scan does too. Otherwise the `/` of `</` could end the name
+    // before it started.
+    let mut tag_len = offset;
+    while tag_len < file.len() {
+        let file_remainder = &file[tag_len..];
+        if closing_lexers
+            .iter()
+            .any(|lexer| lexer(file_remainder).is_some())
+        {
+            break;
+        }
+
+        // Step over a whole character, names can contain multibyte characters.
+        tag_len += file_remainder.chars().next().map_or(1, char::len_utf8);
+    }
+
+    Some((String::from(&file[offset..tag_len]), tag_len))
+}
+
+fn lex_tag_open_start(file: &str) -> Option<LexResult> {
+    if let Some((name, len)) = lex_tag_open(file, "<") {
+        return Some((Token::TagOpenStart(name), len));
+    }
+
+    None
+}
+
+fn lex_tag_close_start(file: &str) -> Option<LexResult> {
+    if let Some((name, len)) = lex_tag_open(file, "</") {
+        return Some((Token::TagCloseStart(name), len));
+    }
+
+    None
+}
+
+fn lex_comment(file: &str) -> Option<LexResult> {
+    let comment_closing_tag = "-->";
+    let comment_closing_tag_len = comment_closing_tag.len();
+
+    let comment_opening_tag = "<!--";
+    let comment_opening_tag_len = comment_opening_tag.len();
+
+    if file.starts_with(comment_opening_tag) {
+        let comment_end = file.find(comment_closing_tag);
+        if let Some(index) = comment_end {
+            return Some((
+                Token::Comment(String::from(&file[comment_opening_tag_len..index])),
+                index + comment_closing_tag_len,
+            ));
+        }
+    }
+
+    None
+}
+
+/// Lexes `<?target body?>`. The XML declaration is a special case and is handled by `lex_xml_declaration`.
+fn lex_processing_instruction(file: &str) -> Option<LexResult> {
+    let pi_opening_tag = "<?";
+    let pi_opening_tag_len = pi_opening_tag.len();
+
+    let pi_closing_tag = "?>";
+    let pi_closing_tag_len = pi_closing_tag.len();
+
+    if !file.starts_with(pi_opening_tag) {
+        return None;
+    }
+
+    let index = file[pi_opening_tag_len..].find(pi_closing_tag)? + pi_opening_tag_len;
+    let content = &file[pi_opening_tag_len..index];
+    let (target, body) = content
+        .split_once(is_xml_whitespace)
+        .unwrap_or((content, ""));
+
+    Some((
+        Token::ProcessingInstruction {
+            target: String::from(target),
+            body: String::from(body.trim_start_matches(is_xml_whitespace)),
+        },
+        index + pi_closing_tag_len,
+    ))
+}
+
+/// The content of a CDATA section is kept as is, even when it looks like markup.
+fn lex_cdata(file: &str) -> Option<LexResult> {
+    let cdata_opening_tag = "<![CDATA[";
+    let cdata_opening_tag_len = cdata_opening_tag.len();
+
+    let cdata_closing_tag = "]]>";
+    let cdata_closing_tag_len = cdata_closing_tag.len();
+
+    if file.starts_with(cdata_opening_tag) {
+        let cdata_end = file.find(cdata_closing_tag);
+        if let Some(index) = cdata_end {
+            return Some((
+                Token::CData(String::from(&file[cdata_opening_tag_len..index])),
+                index + cdata_closing_tag_len,
+            ));
+        }
+    }
+
+    None
+}
+
+fn lex_xml_declaration(file: &str) -> Option<LexResult> {
+    let declaration_opening_tag = "<?xml";
+    let declaration_opening_tag_len = declaration_opening_tag.len();
+
+    let declaration_closing_tag = "?>";
+    let declaration_closing_tag_len = declaration_closing_tag.len();
+
+    // `<?xml-stylesheet ...?>` and friends are processing instructions, not the declaration.
+    let body = file.strip_prefix(declaration_opening_tag)?;
+    if !(body.starts_with(declaration_closing_tag) || body.starts_with(is_xml_whitespace)) {
+        return None;
+    }
+
+    let index = file.find(declaration_closing_tag)?;
+    Some((
+        Token::XmlDeclaration(String::from(&file[declaration_opening_tag_len..index])),
+        index + declaration_closing_tag_len,
+    ))
+}
+
+/// A DOCTYPE ends at the first `>` outside of its internal subset `[...]` and outside of quoted values.
+fn lex_doctype(file: &str) -> Option<LexResult> {
+    let doctype_opening_tag = "<!DOCTYPE";
+    let doctype_opening_tag_len = doctype_opening_tag.len();
+
+    if !file.starts_with(doctype_opening_tag) {
+        return None;
+    }
+
+    let mut subset_depth = 0;
+    let mut quote = None;
+    for (index, c) in file.char_indices().skip(doctype_opening_tag_len) {
+        match (quote, c) {
+            (Some(q), _) if c == q => quote = None,
+            (Some(_), _) => (),
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '[') => subset_depth += 1,
+            (None, ']') => subset_depth -= 1,
+            (None, '>') if subset_depth == 0 => {
+                return Some((
+                    Token::Doctype(String::from(&file[doctype_opening_tag_len..index])),
+                    index + 1,
+                ));
+            }
+            _ => (),
+        }
+    }
+
+    None
+}
+
+/// Lexes a quoted attribute value. Everything up to the matching quote belongs to the value, so a `>` or `/>`
+/// inside it never closes the tag. That holds because `lex_tag_token` tries this lexer before the tag lexers.
+/// Outside of a tag quotes are text, so this lexer is only used inside tags.
+fn lex_string(file: &str) -> Option<LexResult> {
+    let offset = 1;
+    // Values can be quoted with either double or single quotes, the other quote is just a character.
+    let string_closing_tag = file.chars().next().filter(|c| ['"', '\''].contains(c))?;
+    let string_end = file[offset..].find(string_closing_tag);
+    if let Some(end_pos) = string_end {
+        let index = end_pos + offset;
+        return Some((Token::String(String::from(&file[1..index])), index + 1));
+    }
+
+    None
+}
+
+/// The length of the XML `Name` at the start of the string, 0 if it doesn't start with one.
+/// A name starts with a letter, `_` or `:`, followed by letters, digits, `-`, `.`, `:` or `_`.
+fn name_len(file: &str) -> usize {
+    if !file.starts_with(|c: char| c.is_alphabetic() || c == '_' || c == ':') {
+        return 0;
+    }
+
+    file.find(|c: char| !(c.is_alphanumeric() || "-.:_".contains(c)))
+        .unwrap_or(file.len())
+}
+
+/// Lexes a key followed by its `=`, like `data-id=` in `<div data-id="1">`. Like XML allows, there can be
+/// whitespace around the `=`, which belongs to the key so the value is the next token.
+/// Outside of a tag `a=b` is text, so this lexer is only used inside tags.
+fn lex_key(file: &str) -> Option<LexResult> {
+    let index = name_len(file);
+    let equals =
+        index + file[index..].len() - file[index..].trim_start_matches(is_xml_whitespace).len();
+    if index == 0 || !file[equals..].starts_with('=') {
+        return None;
+    }
+    let value = file.len()
+        - file[equals + 1..]
+            .trim_start_matches(is_xml_whitespace)
+            .len();
+
+    Some((Token::Key(String::from(&file[..index])), value))
+}
+
+/// Lexes a key without a value, like `disabled` in `<input disabled>`.
+/// Outside of a tag a bare word is text, so this lexer is only used inside tags.
+fn lex_bare_key(file: &str) -> Option<LexResult> {
+    let index = name_len(file);
+    let rest = &file[index..];
+    if index == 0
+        || !(rest.is_empty()
+            || rest.starts_with(is_xml_whitespace)
+            || rest.starts_with('>')
+            || rest.starts_with("/>"))
+    {
+        return None;
+    }
+
+    Some((Token::Key(String::from(&file[..index])), index))
+}
+
+fn lex_tag_self_closing(file: &str) -> Option<LexResult> {
+    file.starts_with("/>")
+        .then_some((Token::TagSelfClosing, "/>".len()))
+}
+
+fn lex_tag_closing(file: &str) -> Option<LexResult> {
+    file.starts_with('>').then_some((Token::TagClosing, 1))
+}
+
+/// Lexes a space or tab, line endings are lexed by `lex_newline` so the two cover all XML whitespace.
+fn lex_whitespace(file: &str) -> Option<LexResult> {
+    file.starts_with([' ', '\t'])
+        .then_some((Token::Whitespace, 1))
+}
+
+/// Lexes a line ending, either `\n`, `\r\n` or a lone `\r`.
+fn lex_newline(file: &str) -> Option<LexResult> {
+    ["\r\n", "\n", "\r"]
+        .into_iter()
+        .find(|newline| file.starts_with(newline))
+        .map(|newline| (Token::Newline, newline.len()))
+}
+
+/// Lexes the text between tags. A `>` or `/>` outside of a tag is just a character of the text.
+fn lex_text(file: &str) -> Option<LexResult> {
+    // If any of these lexers return a token, then we've reached the end of this text token.
+    let closing_lexers = [
+        lex_tag_open_start,
+        lex_tag_close_start,
+        lex_comment,
+        lex_processing_instruction,
+        lex_cdata,
+    ];
+
+    lex_text_until(file, &closing_lexers)
+}
+
+/// Lexes something inside a tag that isn't a key, value or the end of the tag, like the `$` in `<a $>`, as text.
+/// It ends where the next token of the tag starts.
+fn lex_tag_text(file: &str) -> Option<LexResult> {
+    let closing_lexers = [
+        lex_tag_self_closing,
+        lex_tag_closing,
+        lex_whitespace,
+        lex_newline,
+        lex_comment,
+    ];
+
+    lex_text_until(file, &closing_lexers)
+}
+
+fn lex_text_until(
+    file: &str,
+    closing_lexers: &[fn(&str) -> Option<LexResult>],
+) -> Option<LexResult> {
+    let mut txt_body_len = 0;
+    while txt_body_len < file.len() {
+        let file_remainder = &file[txt_body_len..];
+        if closing_lexers
+            .iter()
+            .any(|lexer| lexer(file_remainder).is_some())
+        {
+            break;
+        }
+
+        txt_body_len += file_remainder.chars().next().map_or(1, char::len_utf8);
+    }
+
+    // Like an XML parser, normalize the line endings inside text to `\n`.
+    let text = file[0..txt_body_len]
+        .replace("\r\n", "\n")
+        .replace('\r', "\n");
+    Some((Token::Text(text), txt_body_len))
+}
+
+/// Lexes the next token outside of a tag.
+fn lex_token(file: &str) -> Option<LexResult> {
+    let lexers = [
+        lex_comment,
+        lex_xml_declaration,
+        lex_processing_instruction,
+        lex_cdata,
+        lex_doctype,
+        lex_tag_close_start,
+        lex_tag_open_start,
+        lex_whitespace,
+        lex_newline,
+        lex_text,
+    ];
+
+    lexers.iter().find_map(|lexer| lexer(file))
+}
+
+/// Lexes the next token between the name of a tag and its `>` or `/>`.
+fn lex_tag_token(file: &str) -> Option<LexResult> {
+    let lexers = [
+        lex_comment,
+        lex_key,
+        lex_bare_key,
+        lex_string,
+        lex_tag_self_closing,
+        lex_tag_closing,
+        lex_whitespace,
+        lex_newline,
+        lex_tag_text,
+    ];
+
+    lexers.iter().find_map(|lexer| lexer(file))
+}
+
+/// Moves a 1-based line and column past the consumed text.
+fn advance_position(line: usize, column: usize, consumed: &str) -> (usize, usize) {
+    match consumed.rfind('\n') {
+        Some(index) => (
+            line + consumed.matches('\n').count(),
+            consumed[index + 1..].chars().count() + 1,
+        ),
+        None => (line, column + consumed.chars().count()),
+    }
+}
+
+/// The number of characters of the remaining input that are shown in a [`LexError`].
+const SNIPPET_LENGTH: usize = 20;
+
+/// Lexes the whole input at once, failing on the first part that can't be lexed.
+pub fn lex_tokens(file: &str) -> Result<Vec<Token>, LexError> {
+    Tokens::new(file).collect()
+}
+
+/// Lexes the attributes of a tag, like the pseudo-attributes of the XML declaration, as if the input were inside
+/// a tag.
+pub fn lex_attribute_tokens(file: &str) -> Result<Vec<Token>, LexError> {
+    Tokens {
+        inside_tag: true,
+        ..Tokens::new(file)
+    }
+    .collect()
+}
+
+/// Lexes the whole input at once like [`lex_tokens`], together with the byte range of every token.
+pub fn lex_spanned_tokens(file: &str) -> Result<Vec<SpannedToken>, LexError> {
+    let mut tokens = Tokens::new(file);
+    let mut spanned = Vec::new();
+    loop {
+        let start = tokens.offset();
+        match tokens.next() {
+            Some(token) => spanned.push((token?, start..tokens.offset())),
+            None => return Ok(spanned),
+        }
+    }
+}
+
+/// Lazily lexes the input, one token per call to `next`.
+/// After an error the iterator is done, so collecting it gives the same result as [`lex_tokens`].
+pub struct Tokens<'a> {
+    file: &'a str,
+    /// The byte offset of the input that is left to lex, and its 1-based position in the file.
+    cursor: usize,
+    line: usize,
+    column: usize,
+    /// Whether the cursor is between the name of a tag and its `>` or `/>`.
+    inside_tag: bool,
+    done: bool,
+}
+
+impl<'a> Tokens<'a> {
+    pub fn new(file: &'a str) -> Tokens<'a> {
+        Tokens {
+            file,
+            cursor: 0,
+            line: 1,
+            column: 1,
+            inside_tag: false,
+            done: false,
+        }
+    }
+
+    /// The byte offset in the input where the next token starts.
+    pub fn offset(&self) -> usize {
+        self.cursor
+    }
+}
+
+impl Iterator for Tokens<'_> {
+    type Item = Result<Token, LexError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let file_to_lex = &self.file[self.cursor..];
+        let lexed = if self.inside_tag {
+            lex_tag_token(file_to_lex)
+        } else {
+            lex_token(file_to_lex)
+        };
+        // Without its `-->`, a comment would be lexed as a tag named `!--` instead.
+        if file_to_lex.starts_with("<!--") && !matches!(lexed, Some((Token::Comment(_), _))) {
+            self.done = true;
+            return Some(Err(LexError::UnterminatedComment {
+                line: self.line,
+                column: self.column,
+            }));
+        }
+        let Some((token, len)) = lexed else {
+            self.done = true;
+            return Some(Err(LexError::UnexpectedString {
+                line: self.line,
+                column: self.column,
+                snippet: file_to_lex
+                    .lines()
+                    .next()
+                    .unwrap_or_default()
+                    .chars()
+                    .take(SNIPPET_LENGTH)
+                    .collect(),
+            }));
+        };
+        match token {
+            Token::TagOpenStart(_) | Token::TagCloseStart(_) => self.inside_tag = true,
+            Token::TagClosing | Token::TagSelfClosing => self.inside_tag = false,
+            _ => (),
+        }
+
+        (self.line, self.column) = advance_position(self.line, self.column, &file_to_lex[..len]);
+        self.cursor += len;
+        self.done = self.cursor >= self.file.len();
+
+        Some(Ok(token))
+    }
+}
+
+/// Whether the input starts with a construct that is only lexed right once its end has been read, like a comment
+/// without its `-->` yet. Until then, the other lexers would take its start for a tag or text.
+fn is_incomplete(file: &str, inside_tag: bool) -> bool {
+    let unfinished = |start: &str, lexer: fn(&str) -> Option<LexResult>| {
+        file.starts_with(start) && lexer(file).is_none()
+    };
+
+    // A key followed by nothing but whitespace yet might still get its `=`.
+    let name = name_len(file);
+    let unfinished_key = name > 0 && file[name..].chars().all(is_xml_whitespace);
+
+    // Outside of a tag, quotes and keys are text.
+    inside_tag && (unfinished("\"", lex_string) || unfinished("'", lex_string) || unfinished_key)
+        || unfinished("<!--", lex_comment)
+        || unfinished("<![CDATA[", lex_cdata)
+        || unfinished("<!DOCTYPE", lex_doctype)
+        || unfinished("<?", lex_processing_instruction)
+}
+
+/// The strings of which one has to be read before the token at the start of the input can end, when it's known
+/// from how the token starts. Text ends at a `<`, or at the `>` of a comment or tag that started in it.
+fn terminators(file: &str, inside_tag: bool) -> Option<&'static [&'static str]> {
+    if file.starts_with("<!--") {
+        Some(&["-->"])
+    } else if file.starts_with("<![CDATA[") {
+        Some(&["]]>"])
+    } else if file.starts_with("<?") {
+        Some(&["?>"])
+    } else if inside_tag {
+        match file.chars().next() {
+            Some('"') => Some(&["\""]),
+            Some('\'') => Some(&["'"]),
+            _ => None,
+        }
+    } else if file.starts_with(|c: char| c != '<' && !is_xml_whitespace(c)) {
+        Some(&["<", ">"])
+    } else {
+        None
+    }
+}
+
+/// Lexes a document that arrives in chunks, like [`Tokens`] does for a whole string, together with the byte
+/// range of every token like [`lex_spanned_tokens`]. Only the chunks that hold the token that is being lexed are
+/// kept, so the memory it takes depends on the longest token instead of the length of the document.
+///
+/// A token that reaches the end of what has been read so far might continue in the next chunk, so it's only
+/// returned once the next chunk is read. Lex errors wait for the end of the document for the same reason.
+pub struct ChunkedTokens<I> {
+    chunks: I,
+    /// The chunks that haven't been lexed completely, starting at byte `start` of the document.
+    buffer: String,
+    start: usize,
+    cursor: usize,
+    line: usize,
+    column: usize,
+    inside_tag: bool,
+    /// The strings that can end the incomplete token at the cursor, like the `-->` of a comment, together with how
+    /// many bytes after the cursor have been searched for them. The token is only lexed again once a chunk brings
+    /// one of them, so a token that spans many chunks isn't lexed again for every one of them.
+    pending: Option<(&'static [&'static str], usize)>,
+    /// Whether the last chunk has been read.
+    end: bool,
+    done: bool,
+}
+
+impl<I> ChunkedTokens<I> {
+    pub fn new(chunks: I) -> ChunkedTokens<I> {
+        ChunkedTokens {
+            chunks,
+            buffer: String::new(),
+            start: 0,
+            cursor: 0,
+            line: 1,
+            column: 1,
+            inside_tag: false,
+            pending: None,
+            end: false,
+            done: false,
+        }
+    }
+}
+
+impl<I, E> Iterator for ChunkedTokens<I>
+where
+    I: Iterator<Item = Result<String, E>>,
+    E: From<LexError>,
+{
+    type Item = Result<SpannedToken, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            if let Some((terminators, searched)) = self.pending {
+                // A terminator can start in the last bytes that were searched, and the `<` that starts a tag after
+                // text only ends it once the name follows.
+                let new = &self.buffer.as_bytes()[self.cursor + searched.saturating_sub(2)..];
+                let found = terminators.iter().any(|terminator| {
+                    new.windows(terminator.len())
+                        .any(|window| window == terminator.as_bytes())
+                });
+                self.pending = if found || self.end {
+                    None
+                } else {
+                    Some((terminators, self.buffer.len() - self.cursor))
+                };
+            }
+
+            if self.pending.is_some() {
+                // Waits for the next chunk.
+            } else if self.cursor < self.buffer.len() {
+                let mut tokens = Tokens {
+                    file: &self.buffer,
+                    cursor: self.cursor,
+                    line: self.line,
+                    column: self.column,
+                    inside_tag: self.inside_tag,
+                    done: false,
+                };
+                let lexed = tokens.next();
+                let complete = self.end
+                    || matches!(lexed, Some(Ok(_)))
+                        && tokens.cursor < self.buffer.len()
+                        && !is_incomplete(&self.buffer[self.cursor..], self.inside_tag);
+                if complete {
+                    let span = self.start + self.cursor..self.start + tokens.cursor;
+                    (self.cursor, self.line, self.column, self.inside_tag) =
+                        (tokens.cursor, tokens.line, tokens.column, tokens.inside_tag);
+                    return match lexed {
+                        Some(Ok(token)) => Some(Ok((token, span))),
+                        Some(Err(err)) => {
+                            self.done = true;
+                            Some(Err(err.into()))
+                        }
+                        None => None,
+                    };
+                }
+                self.pending = terminators(&self.buffer[self.cursor..], self.inside_tag)
+                    .map(|terminators| (terminators, self.buffer.len() - self.cursor));
+            } else if self.end {
+                self.done = true;
+                return None;
+            }
+
+            match self.chunks.next() {
+                Some(Ok(chunk)) => {
+                    // What has been lexed is dropped before the next chunk is added.
+                    self.buffer.drain(..self.cursor);
+                    self.start += self.cursor;
+                    self.cursor = 0;
+                    self.buffer.push_str(&chunk);
+                }
+                Some(Err(err)) => {
+                    self.done = true;
+                    return Some(Err(err));
+                }
+                None => self.end = true,
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokens_iterator() {
+        let mut tokens = Tokens::new("<a x=\"1\">Text</a>");
+
+        assert_eq!(
+            tokens.next(),
+            Some(Ok(Token::TagOpenStart(String::from("a"))))
+        );
+        assert_eq!(tokens.next(), Some(Ok(Token::Whitespace)));
+        assert_eq!(tokens.next(), Some(Ok(Token::Key(String::from("x")))));
+        assert_eq!(tokens.by_ref().count(), 5);
+        assert_eq!(tokens.next(), None);
+
+        let mut tokens = Tokens::new("<a>\n<!-- never closed");
+        assert_eq!(tokens.by_ref().filter(Result::is_ok).count(), 3);
+        assert_eq!(tokens.next(), None);
+        assert_eq!(
+            Tokens::new("<a>\n<!-- never closed").collect::<Result<Vec<_>, _>>(),
+            lex_tokens("<a>\n<!-- never closed")
+        );
+    }
+
+    /// Lexes the next token, with the remaining input instead of the consumed length to keep the tests readable.
+    fn lex_token_with_remainder(file: &str) -> Option<(Token, String)> {
+        lex_token(file).map(|(token, len)| (token, String::from(&file[len..])))
+    }
+
+    /// Like `lex_token_with_remainder`, for the next token inside a tag.
+    fn lex_tag_token_with_remainder(file: &str) -> Option<(Token, String)> {
+        lex_tag_token(file).map(|(token, len)| (token, String::from(&file[len..])))
+    }
+
+    #[test]
+    fn test_advance_position() {
+        assert_eq!(advance_position(1, 1, "<a>"), (1, 4));
+        assert_eq!(advance_position(1, 4, "\n"), (2, 1));
+        assert_eq!(advance_position(2, 1, "é ü"), (2, 4));
+        assert_eq!(advance_position(2, 4, "text\n  more"), (3, 7));
+    }
+
+    #[test]
+    fn test_lex_error_display() {
+        let err = LexError::UnexpectedString {
+            line: 12,
+            column: 4,
+            snippet: String::from("<<oops"),
+        };
+
+        assert_eq!(err.to_string(), "lex error at 12:4: unexpected '<<oops'");
+    }
+
+    #[test]
+    fn test_lex_next_token_open() {
+        assert_eq!(
+            lex_token_with_remainder("<element />"),
+            Some((
+                Token::TagOpenStart(String::from("element")),
+                String::from(" />")
+            ))
+        );
+
+        assert_eq!(
+            lex_token_with_remainder("<element/>"),
+            Some((
+                Token::TagOpenStart(String::from("element")),
+                String::from("/>")
+            ))
+        );
+
+        assert_eq!(
+            lex_token_with_remainder("</element />"),
+            Some((
+                Token::TagCloseStart(String::from("element")),
+                String::from(" />")
+            ))
+        );
+
+        assert_eq!(
+            lex_token_with_remainder("</element<!-- comment --> />"),
+            Some((
+                Token::TagCloseStart(String::from("element")),
+                String::from("<!-- comment --> />")
+            ))
+        );
+    }
+
+    #[test]
+    fn test_lex_next_token_selfclosing() {
+        assert_eq!(
+            lex_tag_token_with_remainder("/> "),
+            Some((Token::TagSelfClosing, String::from(" ")))
+        );
+    }
+
+    #[test]
+    fn test_lex_next_token_close() {
+        assert_eq!(
+            lex_tag_token_with_remainder("><"),
+            Some((Token::TagClosing, String::from("<")))
+        );
+    }
+
+    #[test]
+    fn test_lex_tag_open_names() {
+        assert_eq!(
+            lex_token_with_remainder("<element>"),
+            Some((
+                Token::TagOpenStart(String::from("element")),
+                String::from(">")
+            ))
+        );
+        assert_eq!(
+            lex_token_with_remainder("</element>"),
+            Some((
+                Token::TagCloseStart(String::from("element")),
+                String::from(">")
+            ))
+        );
+        assert_eq!(
+            lex_token_with_remainder("</a>"),
+            Some((Token::TagCloseStart(String::from("a")), String::from(">")))
+        );
+        assert_eq!(
+            lex_token_with_remainder("</>"),
+            Some((Token::TagCloseStart(String::new()), String::from(">")))
+        );
+        assert_eq!(
+            lex_token_with_remainder("<ns:long-element-name/>"),
+            Some((
+                Token::TagOpenStart(String::from("ns:long-element-name")),
+                String::from("/>")
+            ))
+        );
+    }
+
+    #[test]
+    fn test_lex_comment() {
+        assert_eq!(
+            lex_token_with_remainder("<!-- This is a comment -->"),
+            Some((
+                Token::Comment(String::from(" This is a comment ")),
+                String::from("")
+            ))
+        )
+    }
+
+    #[test]
+    fn test_lex_unterminated_comment() {
+        assert_eq!(
+            lex_tokens("<a>\n  <!-- never closed <b/></a>"),
+            Err(LexError::UnterminatedComment { line: 2, column: 3 })
+        );
+        assert_eq!(
+            LexError::UnterminatedComment { line: 2, column: 3 }.to_string(),
+            "lex error at 2:3: comment is never closed with '-->'"
+        );
+    }
+
+    #[test]
+    fn test_lex_cdata() {
+        assert_eq!(
+            lex_token_with_remainder(
+                "<![CDATA[if (a < b && c > d) { x = \"<tag/>\"; }]]></script>"
+            ),
+            Some((
+                Token::CData(String::from("if (a < b && c > d) { x = \"<tag/>\"; }")),
+                String::from("</script>")
+            ))
+        );
+
+        assert_eq!(
+            lex_tokens("<a>Text<![CDATA[<b>]]></a>"),
+            Ok(vec![
+                Token::TagOpenStart(String::from("a")),
+                Token::TagClosing,
+                Token::Text(String::from("Text")),
+                Token::CData(String::from("<b>")),
+                Token::TagCloseStart(String::from("a")),
+                Token::TagClosing,
+            ])
+        );
+    }
+
+    #[test]
+    fn test_lex_processing_instruction() {
+        assert_eq!(
+            lex_token_with_remainder(
+                "<?xml-stylesheet type=\"text/xsl\" href=\"style.xsl\"?><root/>"
+            ),
+            Some((
+                Token::ProcessingInstruction {
+                    target: String::from("xml-stylesheet"),
+                    body: String::from("type=\"text/xsl\" href=\"style.xsl\"")
+                },
+                String::from("<root/>")
+            ))
+        );
+
+        assert_eq!(
+            lex_tokens("<a>Text<?php echo 1; ?></a>"),
+            Ok(vec![
+                Token::TagOpenStart(String::from("a")),
+                Token::TagClosing,
+                Token::Text(String::from("Text")),
+                Token::ProcessingInstruction {
+                    target: String::from("php"),
+                    body: String::from("echo 1; ")
+                },
+                Token::TagCloseStart(String::from("a")),
+                Token::TagClosing,
+            ])
+        );
+    }
+
+    #[test]
+    fn test_lex_doctype() {
+        assert_eq!(
+            lex_token_with_remainder("<!DOCTYPE html>\n<html/>"),
+            Some((
+                Token::Doctype(String::from(" html")),
+                String::from("\n<html/>")
+            ))
+        );
+
+        assert_eq!(
+            lex_token_with_remainder(
+                "<!DOCTYPE x [\n  <!ENTITY a \"b>]\">\n  <!ELEMENT x EMPTY>\n]><x/>"
+            ),
+            Some((
+                Token::Doctype(String::from(
+                    " x [\n  <!ENTITY a \"b>]\">\n  <!ELEMENT x EMPTY>\n]"
+                )),
+                String::from("<x/>")
+            ))
+        );
+    }
+
+    #[test]
+    fn test_lex_xml_declaration() {
+        assert_eq!(
+            lex_token_with_remainder("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<root/>"),
+            Some((
+                Token::XmlDeclaration(String::from(" version=\"1.0\" encoding=\"UTF-8\"")),
+                String::from("\n<root/>")
+            ))
+        );
+
+        assert_eq!(
+            lex_tokens("<?xml version=\"1.0\"?>\n<root/>"),
+            Ok(vec![
+                Token::XmlDeclaration(String::from(" version=\"1.0\"")),
+                Token::Newline,
+                Token::TagOpenStart(String::from("root")),
+                Token::TagSelfClosing,
+            ])
+        );
+    }
+
+    #[test]
+    fn test_lex_xml_declaration_is_not_other_processing_instruction() {
+        assert_eq!(
+            lex_xml_declaration("<?xml-stylesheet href=\"style.css\"?>"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_lex_next_token_string() {
+        assert_eq!(
+            lex_tag_token_with_remainder("\"string content\" />"),
+            Some((
+                Token::String(String::from("string content")),
+                String::from(" />")
+            ))
+        );
+    }
+
+    #[test]
+    fn test_lex_spanned_tokens() {
+        let file = "<a x=\"1\">é</a>";
+        let tokens = lex_spanned_tokens(file).unwrap();
+
+        assert_eq!(
+            tokens
+                .iter()
+                .map(|(_, span)| &file[span.clone()])
+                .collect::<Vec<_>>(),
+            ["<a", " ", "x=", "\"1\"", ">", "é", "</a", ">"]
+        );
+        assert_eq!(
+            tokens
+                .into_iter()
+                .map(|(token, _)| token)
+                .collect::<Vec<_>>(),
+            lex_tokens(file).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_chunked_tokens() {
+        let files = [
+            "<?xml version=\"1.0\"?>\r\n<!DOCTYPE a [ <!ENTITY e \"x\"> ]>\n<a x='1' y=\"a > b\" z w = \"2\">\
+            <!-- a <comment> -->text é, 1 < 2 <!-- x --> y<![CDATA[<b>]]><?pi body?><c/></a>",
+            "<a>\n<!-- never closed",
+        ];
+        for file in files {
+            // Chunks of bytes would split the `é`, which decoding never does.
+            let chars: Vec<String> = file.chars().map(String::from).collect();
+            for chunk_size in [1, 2, 3, 5, 8, 13, chars.len()] {
+                let chunks = chars
+                    .chunks(chunk_size)
+                    .map(|chunk| Ok::<_, LexError>(chunk.concat()));
+                let chunked: Result<Vec<_>, _> = ChunkedTokens::new(chunks).collect();
+
+                assert_eq!(
+                    chunked,
+                    lex_spanned_tokens(file),
+                    "{:?} in chunks of {}",
+                    file,
+                    chunk_size
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_lex_mixed_whitespace() {
+        assert_eq!(
+            lex_tokens("<?pi\tbody?><a\tx=\"1\"\r\n disabled\t/>\t \r\n<b>\u{a0}</b>"),
+            Ok(vec![
+                Token::ProcessingInstruction {
+                    target: String::from("pi"),
+                    body: String::from("body"),
+                },
+                Token::TagOpenStart(String::from("a")),
+                Token::Whitespace,
+                Token::Key(String::from("x")),
+                Token::String(String::from("1")),
+                Token::Newline,
+                Token::Whitespace,
+                Token::Key(String::from("disabled")),
+                Token::Whitespace,
+                Token::TagSelfClosing,
+                Token::Whitespace,
+                Token::Whitespace,
+                Token::Newline,
+                Token::TagOpenStart(String::from("b")),
+                Token::TagClosing,
+                // A non-breaking space isn't XML whitespace, so it's text.
+                Token::Text(String::from("\u{a0}")),
+                Token::TagCloseStart(String::from("b")),
+                Token::TagClosing,
+            ])
+        );
+    }
+
+    #[test]
+    fn test_lex_string_with_tag_closing() {
+        assert_eq!(
+            lex_tokens("<path d=\"M0 0 L10 10>foo\" e='a/>b'/>"),
+            Ok(vec![
+                Token::TagOpenStart(String::from("path")),
+                Token::Whitespace,
+                Token::Key(String::from("d")),
+                Token::String(String::from("M0 0 L10 10>foo")),
+                Token::Whitespace,
+                Token::Key(String::from("e")),
+                Token::String(String::from("a/>b")),
+                Token::TagSelfClosing,
+            ])
+        );
+    }
+
+    #[test]
+    fn test_lex_markup_characters_in_text() {
+        assert_eq!(
+            lex_tokens("<p>\"a\"='b' 1 > 0/></p >"),
+            Ok(vec![
+                Token::TagOpenStart(String::from("p")),
+                Token::TagClosing,
+                Token::Text(String::from("\"a\"='b' 1 > 0/>")),
+                Token::TagCloseStart(String::from("p")),
+                Token::Whitespace,
+                Token::TagClosing,
+            ])
+        );
+    }
+
+    #[test]
+    fn test_lex_next_token_single_quoted_string() {
+        assert_eq!(
+            lex_tag_token_with_remainder("'string content' />"),
+            Some((
+                Token::String(String::from("string content")),
+                String::from(" />")
+            ))
+        );
+
+        assert_eq!(
+            lex_tag_token_with_remainder("'say \"hi\"'>"),
+            Some((Token::String(String::from("say \"hi\"")), String::from(">")))
+        );
+
+        assert_eq!(
+            lex_tag_token_with_remainder("\"it's\">"),
+            Some((Token::String(String::from("it's")), String::from(">")))
+        );
+
+        assert_eq!(
+            lex_tokens("<a key='value'/>"),
+            Ok(vec![
+                Token::TagOpenStart(String::from("a")),
+                Token::Whitespace,
+                Token::Key(String::from("key")),
+                Token::String(String::from("value")),
+                Token::TagSelfClosing,
+            ])
+        );
+    }
+
+    #[test]
+    fn test_lex_next_token_key() {
+        assert_eq!(
+            lex_token_with_remainder("<element />"),
+            Some((
+                Token::TagOpenStart(String::from("element")),
+                String::from(" />")
+            ))
+        );
+    }
+
+    #[test]
+    fn test_lex_all_tokens() {
+        assert_eq!(
+            lex_tokens("</ <!-- comment --> >"),
+            Ok(vec![
+                Token::TagCloseStart(String::new()),
+                Token::Whitespace,
+                Token::Comment(String::from(" comment ")),
+                Token::Whitespace,
+                Token::TagClosing
+            ])
+        );
+    }
+
+    #[test]
+    fn test_lex_key_names() {
+        for key in [
+            "data-role",
+            "xmlns:foo",
+            "x.y",
+            "aria_label",
+            "_private",
+            ":colon",
+        ] {
+            assert_eq!(
+                lex_key(&format!("{}=\"1\"", key)),
+                Some((Token::Key(String::from(key)), key.len() + 1))
+            );
+            assert_eq!(
+                lex_bare_key(&format!("{}>", key)),
+                Some((Token::Key(String::from(key)), key.len()))
+            );
+        }
+
+        assert_eq!(lex_key("1st=\"1\""), None);
+        assert_eq!(lex_key("-x=\"1\""), None);
+        assert_eq!(lex_key("a$b=\"1\""), None);
+        assert_eq!(lex_bare_key(".hidden>"), None);
+    }
+
+    #[test]
+    fn test_lex_key_with_whitespace() {
+        assert_eq!(
+            lex_tokens("<a b = \"1\" c=\n'2' d\t=\"3\"/>"),
+            Ok(vec![
+                Token::TagOpenStart(String::from("a")),
+                Token::Whitespace,
+                Token::Key(String::from("b")),
+                Token::String(String::from("1")),
+                Token::Whitespace,
+                Token::Key(String::from("c")),
+                Token::String(String::from("2")),
+                Token::Whitespace,
+                Token::Key(String::from("d")),
+                Token::String(String::from("3")),
+                Token::TagSelfClosing,
+            ])
+        );
+    }
+
+    #[test]
+    fn test_lex_bare_keys() {
+        assert_eq!(
+            lex_tokens("<input disabled required/>"),
+            Ok(vec![
+                Token::TagOpenStart(String::from("input")),
+                Token::Whitespace,
+                Token::Key(String::from("disabled")),
+                Token::Whitespace,
+                Token::Key(String::from("required")),
+                Token::TagSelfClosing,
+            ])
+        );
+
+        let tokens = lex_tokens("<option selected>Bare words</option>").unwrap();
+        assert_eq!(tokens[2], Token::Key(String::from("selected")));
+        assert_eq!(tokens[4], Token::Text(String::from("Bare words")));
+    }
+
+    #[test]
+    fn test_lex_multibyte_characters() {
+        assert_eq!(
+            lex_tokens("<café prénom=\"é\">naïve 日本語</café>"),
+            Ok(vec![
+                Token::TagOpenStart(String::from("café")),
+                Token::Whitespace,
+                Token::Key(String::from("prénom")),
+                Token::String(String::from("é")),
+                Token::TagClosing,
+                Token::Text(String::from("naïve 日本語")),
+                Token::TagCloseStart(String::from("café")),
+                Token::TagClosing,
+            ])
+        );
+
+        assert_eq!(
+            lex_tokens("<日本 élan=\"1\"/>"),
+            Ok(vec![
+                Token::TagOpenStart(String::from("日本")),
+                Token::Whitespace,
+                Token::Key(String::from("élan")),
+                Token::String(String::from("1")),
+                Token::TagSelfClosing,
+            ])
+        );
+    }
+
+    #[test]
+    fn test_lex_all_tokens_from_file() {
+        let file = String::from(
+            "
+        <tag>
+            <child key=\"1\">Content</child>
+            <child>More Content</child>
+            <selfclosingchild/>
+        </tag>
+        ",
+        );
+        let result = lex_tokens(&file);
+
+        assert!(result.is_ok());
+    }
+}
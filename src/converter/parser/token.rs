@@ -1,4 +1,12 @@
-#[derive(Debug, PartialEq, Eq)]
+#[cfg(feature = "serde")]
+use serde::Serialize;
+use std::ops::Range;
+
+/// A token together with the byte range of the input it was lexed from.
+pub type SpannedToken = (Token, Range<usize>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub enum Token {
     TagOpenStart(String),
     TagCloseStart(String),
@@ -7,6 +15,18 @@ pub enum Token {
     Key(String),
     String(String),
     Comment(String),
+    CData(String),
+    XmlDeclaration(String),
+    Doctype(String),
+    ProcessingInstruction { target: String, body: String },
     Whitespace,
+    Newline,
     Text(String), // Different from a String in the sence that a String is surrounded by double qoutes and Text is not.
 }
+
+/// A token without a position, for graphs that are built from tokens that weren't lexed from a document.
+impl From<Token> for SpannedToken {
+    fn from(token: Token) -> SpannedToken {
+        (token, 0..0)
+    }
+}
@@ -0,0 +1,23 @@
+//! Generates an overview of an XML file, by merging repeated elements into a single instance
+//! that shows every attribute and child they have.
+
+pub mod converter;
+
+pub use converter::{
+    ConvertError, Graph, GraphError, LexError, Node, SpannedToken, Token, Tokens,
+    lex_spanned_tokens, lex_tokens,
+};
+
+/// The settings of [`generate_overview`]. This is an alias of [`converter::Config`], not a separate type, so
+/// the presets and every field of the config are available under this name.
+pub type Options = converter::Config;
+
+/// Builds [`Options`]. This is an alias of [`converter::ConfigBuilder`].
+pub type OptionsBuilder = converter::ConfigBuilder;
+
+/// Generates the overview of an XML document, like the binary prints it for a file.
+pub fn generate_overview(xml: &str, options: Options) -> Result<String, ConvertError> {
+    let graph = converter::build_graph(xml, &options)?;
+
+    Ok(graph.print(&options))
+}
@@ -1,33 +1,392 @@
-use clap::Parser;
+use clap::{Parser, ValueEnum};
+use std::fs;
+use std::io::{IsTerminal, Read, Write};
 use std::path::PathBuf;
 
-mod converter;
+use xml_overview::converter;
+
+#[derive(Clone, Copy, ValueEnum)]
+enum OutputFormat {
+    /// The minimized XML overview.
+    Xml,
+    /// The minimized tree as JSON.
+    #[cfg(feature = "serde")]
+    Json,
+    /// A Draft-07 JSON Schema derived from the minimized tree.
+    #[cfg(feature = "serde")]
+    JsonSchema,
+    /// The minimized tree as YAML.
+    Yaml,
+    /// The minimized tree as a Graphviz digraph.
+    Dot,
+    /// Every distinct element path, like `/catalog/book/@id`, one per line.
+    Paths,
+    /// A Markdown table listing every element with its attributes and children.
+    Markdown,
+    /// A rough XSD schema derived from the minimized tree.
+    Xsd,
+    /// A DTD with the content model and attributes of every distinct element.
+    Dtd,
+    /// Rust structs with serde derives, one for every distinct element.
+    Rust,
+    /// TypeScript interfaces, one for every distinct element.
+    Typescript,
+    /// The minimized graph as a MessagePack snapshot.
+    #[cfg(feature = "serde")]
+    Msgpack,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Preset {
+    /// Defaults for SVG images, hides the namespace declarations and cuts off long values like path data.
+    Svg,
+    /// Defaults for XHTML pages, accepts HTML void elements like `<br>` without a closing tag.
+    Xhtml,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Strategy {
+    /// Keep the instance with the most children.
+    MostChildren,
+    /// Keep the first instance in the document.
+    First,
+    /// Combine the attributes and children of every instance with the same name.
+    Merge,
+}
+
+impl From<Strategy> for converter::MinimizeStrategy {
+    fn from(strategy: Strategy) -> converter::MinimizeStrategy {
+        match strategy {
+            Strategy::MostChildren => converter::MinimizeStrategy::MostChildren,
+            Strategy::First => converter::MinimizeStrategy::First,
+            Strategy::Merge => converter::MinimizeStrategy::Merge,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum LogLevel {
+    /// Log nothing.
+    Off,
+    /// Log the number of tokens and tags after every step.
+    Info,
+    /// Also log every tag that is read and every group of sibling tags that is collapsed or kept.
+    Debug,
+}
+
+impl From<LogLevel> for converter::LogLevel {
+    fn from(log_level: LogLevel) -> converter::LogLevel {
+        match log_level {
+            LogLevel::Off => converter::LogLevel::Off,
+            LogLevel::Info => converter::LogLevel::Info,
+            LogLevel::Debug => converter::LogLevel::Debug,
+        }
+    }
+}
+
+/// The maximum length of values when neither `--max-value-len` nor a preset sets it.
+const DEFAULT_MAX_VALUE_LEN: usize = 40;
 
 #[derive(Parser)]
 #[command(name = "XML Overview")]
 #[command(version)]
 #[command(about = "Generates an overview of an XML file.")]
 struct Args {
-    /// The XML file to be converted.
+    /// (Optional) The XML file to be converted, leave empty or use `-` to read from stdin.
+    /// Pass it multiple times to merge similar files into one overview.
+    /// With the `http` feature, an `http://` or `https://` URL is downloaded instead.
     #[arg(short, long)]
-    file: PathBuf,
+    file: Vec<PathBuf>,
 
-    /// (Optional) The maximum depth of the XML tree that should be considered. 
+    /// (Optional) The maximum depth of the XML tree that should be considered.
     /// Leave empty to read the whole XML structure.
     #[arg(short, long)]
     depth: Option<usize>,
 
+    /// (Optional) The maximum number of child tags to print per tag, the rest are replaced by a comment that says how
+    /// many were left out. Leave empty to print all of them.
+    #[arg(long)]
+    max_children: Option<usize>,
+
     /// (Optional) The path of the output file where the XML overview should be written, leave empty to print to stdout.
     #[arg(short, long)]
     output: Option<PathBuf>,
 
-    /// (Optional) Show extra comments in the overview that give extra information related to the original XML, like how many XML tags were omitted in a certain position.
-    #[arg(short, long, default_value_t=false)]
+    /// (Optional) Show extra comments in the overview that give extra information related to the original XML, like how many XML tags were omitted in a certain position. Also keeps the comments of the original XML.
+    #[arg(short, long, default_value_t = false)]
     verbose: bool,
+
+    /// (Optional) Only print the elements with this name and their subtrees, for the xml format. Use a path like `catalog/book` to only print the elements at that path.
+    #[arg(long)]
+    element: Option<String>,
+
+    /// (Optional) Only keep the tags at this path, like `catalog/book/price`, together with their children and the tags that lead to them. Pass it multiple times to keep several paths.
+    #[arg(long)]
+    only: Vec<String>,
+
+    /// (Optional) The format in which the overview should be written.
+    #[arg(long, value_enum, default_value_t = OutputFormat::Xml)]
+    format: OutputFormat,
+
+    /// (Optional) Only collapse groups of matching sibling tags that have at least this many instances, smaller groups are shown as they are.
+    #[arg(long)]
+    collapse_threshold: Option<usize>,
+
+    /// (Optional) Annotate elements with a regex-like cardinality, `?` for optional, `*` for zero or more and `+` for one or more. Elements that are empty in some places and not in others are marked as sometimes empty.
+    #[arg(long, default_value_t = false)]
+    cardinality: bool,
+
+    /// (Optional) Show how many times each element occurs in the document, like `(×37)`.
+    #[arg(long, default_value_t = false)]
+    counts: bool,
+
+    /// (Optional) Put every element on its own line, indented by this many spaces per level, instead of keeping the whitespace of the XML. Use 0 to keep the whitespace of the XML.
+    #[arg(long)]
+    indent: Option<usize>,
+
+    /// (Optional) Collapse runs of spaces and newlines into a single space or newline.
+    #[arg(long, default_value_t = false)]
+    normalize_whitespace: bool,
+
+    /// (Optional) Keep entity references like `&#169;` in text as they are, instead of decoding them.
+    #[arg(long, default_value_t = false)]
+    raw_text: bool,
+
+    /// (Optional) Mark tags that contain both child tags and text with a `<!-- mixed content -->` comment.
+    #[arg(long, default_value_t = false)]
+    show_mixed: bool,
+
+    /// (Optional) Leave the `xmlns` and `xmlns:prefix` attributes out of the tags and list them once at the top instead.
+    #[arg(long, default_value_t = false)]
+    hide_namespaces: bool,
+
+    /// (Optional) Don't mark attributes that only some of the merged tags have with a `?`, like `label?=""`.
+    #[arg(long, default_value_t = false)]
+    plain_attributes: bool,
+
+    /// (Optional) Print the attributes of every tag sorted by name instead of in the order of the XML, for output that diffs well.
+    #[arg(long, default_value_t = false)]
+    sort_attrs: bool,
+
+    /// (Optional) Leave out the sample values of attributes, printing them as `key=""`.
+    #[arg(long, default_value_t = false)]
+    strip_values: bool,
+
+    /// (Optional) Show the inferred type of attribute values and of the text of tags without child tags, like `{int}` or `{date}`, instead of a sample value.
+    #[arg(long, default_value_t = false)]
+    types: bool,
+
+    /// (Optional) Exit with a non-zero status code when any warnings are emitted while reading the XML.
+    #[arg(long, default_value_t = false)]
+    fail_on_warning: bool,
+
+    /// (Optional) Cut off attribute values and text longer than this many characters, use 0 to show them in full.
+    /// Defaults to 40, or to the length of the preset.
+    #[arg(long)]
+    max_value_len: Option<usize>,
+
+    /// (Optional) Leave out all text, even text that isn't whitespace, so tags with only text become `<name/>`.
+    #[arg(long, default_value_t = false)]
+    no_text: bool,
+
+    /// (Optional) Don't collapse matching sibling tags, print every tag in the XML instead.
+    #[arg(long, default_value_t = false)]
+    no_minimize: bool,
+
+    /// (Optional) Fail on a closing tag that doesn't match the open tag, instead of recovering from it with a warning.
+    #[arg(long, default_value_t = false)]
+    strict: bool,
+
+    /// (Optional) How a group of matching sibling tags is collapsed into one. Defaults to most-children.
+    #[arg(long, value_enum)]
+    strategy: Option<Strategy>,
+
+    /// (Optional) Print summary metrics of the XML, like the maximum depth, instead of the overview.
+    #[arg(long, default_value_t = false)]
+    stats: bool,
+
+    /// (Optional) Print how many times every tag name occurs in the XML, the most frequent first, instead of the overview.
+    #[arg(long, default_value_t = false)]
+    count_only: bool,
+
+    /// (Optional) Print only the tags without child tags, the fields that hold the data, each with the path that leads to it like `catalog/book/price`, instead of the overview.
+    #[arg(long, default_value_t = false)]
+    leaves_only: bool,
+
+    /// (Optional) Log what the conversion is doing to stderr, the overview on stdout stays the same. Defaults to off.
+    #[arg(long, value_enum)]
+    log_level: Option<LogLevel>,
+
+    /// (Optional) Print the tokens the XML is lexed into, one per line, instead of the overview. Useful to find out why an XML gives a surprising overview.
+    #[arg(long, default_value_t = false)]
+    tokens: bool,
+
+    /// (Optional) Don't color the overview, even on a terminal. The overview is never colored when it's written to a file or a pipe, or when the `NO_COLOR` environment variable is set.
+    #[arg(long, default_value_t = false)]
+    no_color: bool,
+
+    /// (Optional) The number of seconds a download of a URL may take, leave empty to wait as long as it takes.
+    #[cfg(feature = "http")]
+    #[arg(long)]
+    timeout: Option<u64>,
+
+    /// (Optional) Start from the default settings for a common XML dialect. The other options override the settings of
+    /// the preset when they're given.
+    #[arg(long, value_enum)]
+    preset: Option<Preset>,
 }
 
 fn main() {
     let args = Args::parse();
 
-    converter::convert(&args.file);
-}
\ No newline at end of file
+    let preset = match args.preset {
+        Some(Preset::Svg) => converter::Config::svg(),
+        Some(Preset::Xhtml) => converter::Config::xhtml(),
+        None => converter::Config {
+            max_value_len: DEFAULT_MAX_VALUE_LEN,
+            ..converter::Config::default()
+        },
+    };
+    // An option only overrides the preset when it's given, a flag only when it's set.
+    let config = converter::ConfigBuilder::from(preset.clone())
+        .cardinality(args.cardinality || preset.cardinality)
+        .collapse_threshold(args.collapse_threshold.unwrap_or(preset.collapse_threshold))
+        .counts(args.counts || preset.counts)
+        .depth(args.depth.or(preset.depth))
+        .max_children(args.max_children.or(preset.max_children))
+        .hide_namespaces(args.hide_namespaces || preset.hide_namespaces)
+        .indent(args.indent.unwrap_or(preset.indent))
+        .log_level(
+            args.log_level
+                .map_or(preset.log_level, converter::LogLevel::from),
+        )
+        .max_value_len(args.max_value_len.unwrap_or(preset.max_value_len))
+        .no_minimize(args.no_minimize || preset.no_minimize)
+        .no_text(args.no_text || preset.no_text)
+        .strategy(
+            args.strategy
+                .map_or(preset.minimize_strategy, converter::MinimizeStrategy::from),
+        )
+        .normalize_whitespace(args.normalize_whitespace || preset.normalize_whitespace)
+        .only(if args.only.is_empty() {
+            preset.only
+        } else {
+            args.only
+        })
+        .plain_keys(args.plain_attributes || preset.plain_keys)
+        .raw_text(args.raw_text || preset.raw_text)
+        .show_mixed(args.show_mixed || preset.show_mixed)
+        .strict(args.strict || preset.strict)
+        .sort_attributes(args.sort_attrs || preset.sort_attributes)
+        .strip_values(args.strip_values || preset.strip_values)
+        .types(args.types || preset.types)
+        .verbose(args.verbose || preset.verbose)
+        .color(
+            !args.no_color
+                && args.output.is_none()
+                && std::env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+                && std::io::stdout().is_terminal(),
+        )
+        .build();
+
+    let paths = if args.file.is_empty() {
+        vec![PathBuf::from("-")]
+    } else {
+        args.file.clone()
+    };
+    let is_stdin = |path: &PathBuf| path.as_os_str() == "-";
+    // The converter reads the files itself, so large files are read in chunks. Stdin and URLs are read up front.
+    let stdin = if paths.iter().any(is_stdin) {
+        let mut bytes = Vec::new();
+        std::io::stdin()
+            .read_to_end(&mut bytes)
+            .and_then(|_| converter::decode(&bytes))
+            .unwrap_or_else(|err| {
+                eprintln!("Error: Failed to read stdin: {}", err);
+                std::process::exit(1);
+            })
+    } else {
+        String::new()
+    };
+    #[cfg(feature = "http")]
+    let downloads: Vec<Option<String>> = paths
+        .iter()
+        .map(|path| {
+            let url = path.to_str().filter(|path| converter::is_url(path))?;
+            let timeout = args.timeout.map(std::time::Duration::from_secs);
+            Some(converter::fetch(url, timeout).unwrap_or_else(|err| {
+                eprintln!("Error: {}", err);
+                std::process::exit(1);
+            }))
+        })
+        .collect();
+    #[cfg(not(feature = "http"))]
+    let downloads: Vec<Option<String>> = vec![None; paths.len()];
+    let documents: Vec<converter::Document> = paths
+        .iter()
+        .zip(&downloads)
+        .map(|(path, download)| match download {
+            Some(xml) => converter::Document::Str(xml),
+            None if is_stdin(path) => converter::Document::Str(&stdin),
+            None => converter::Document::File(path),
+        })
+        .collect();
+
+    let text =
+        |(text, warnings): (String, Vec<String>)| (format!("{}\n", text).into_bytes(), warnings);
+    let conversion = if args.stats {
+        converter::convert_stats(&documents, &config)
+            .map(|(stats, warnings)| text((stats.to_string(), warnings)))
+    } else if args.count_only {
+        converter::convert_name_frequencies(&documents, &config).map(text)
+    } else if args.leaves_only {
+        converter::convert_leaf_paths(&documents, &config).map(text)
+    } else if args.tokens {
+        converter::convert_tokens(&documents).map(text)
+    } else {
+        match args.format {
+            OutputFormat::Xml => match &args.element {
+                Some(name) => converter::convert_element(&documents, name, &config).map(text),
+                None => converter::convert_merged(&documents, &config).map(text),
+            },
+            #[cfg(feature = "serde")]
+            OutputFormat::Json => converter::convert_json(&documents, &config).map(text),
+            #[cfg(feature = "serde")]
+            OutputFormat::JsonSchema => {
+                converter::convert_json_schema(&documents, &config).map(text)
+            }
+            OutputFormat::Yaml => converter::convert_yaml(&documents, &config).map(text),
+            OutputFormat::Dot => converter::convert_dot(&documents, &config).map(text),
+            OutputFormat::Paths => converter::convert_paths(&documents, &config).map(text),
+            OutputFormat::Markdown => converter::convert_markdown(&documents, &config).map(text),
+            OutputFormat::Xsd => converter::convert_xsd(&documents, &config).map(text),
+            OutputFormat::Dtd => converter::convert_dtd(&documents, &config).map(text),
+            OutputFormat::Rust => converter::convert_rust(&documents, &config).map(text),
+            OutputFormat::Typescript => {
+                converter::convert_typescript(&documents, &config).map(text)
+            }
+            #[cfg(feature = "serde")]
+            OutputFormat::Msgpack => converter::convert_msgpack(&documents, &config),
+        }
+    };
+    let (output, warnings) = conversion.unwrap_or_else(|err| {
+        eprintln!("Error: {}", err);
+        std::process::exit(1);
+    });
+
+    match &args.output {
+        Some(path) => fs::write(path, output)
+            .unwrap_or_else(|_| panic!("Failed to write output file: {:?}", path)),
+        None => std::io::stdout()
+            .write_all(&output)
+            .expect("Failed to write to stdout"),
+    }
+
+    for warning in &warnings {
+        eprintln!("Warning: {}", warning);
+    }
+
+    if args.fail_on_warning && !warnings.is_empty() {
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,51 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Runs the binary with the arguments on the XML from stdin and returns what it prints to stdout.
+fn run(args: &[&str], xml: &str) -> String {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_xml-overview"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(xml.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_preset_svg() {
+    let xml =
+        "<svg xmlns=\"http://www.w3.org/2000/svg\"><path d=\"M0 0 L10 10 L20 0 L30 10 Z\"/></svg>";
+
+    assert_eq!(run(&[], xml), format!("{}\n", xml));
+    assert_eq!(
+        run(&["--preset", "svg"], xml),
+        "<!-- xmlns=\"http://www.w3.org/2000/svg\" -->\n<svg><path d=\"M0 0 L10 10 L20 0 L3…\"/></svg>\n"
+    );
+    // Options that are given override the preset.
+    assert_eq!(
+        run(&["--preset", "svg", "--max-value-len", "0"], xml),
+        "<!-- xmlns=\"http://www.w3.org/2000/svg\" -->\n<svg><path d=\"M0 0 L10 10 L20 0 L30 10 Z\"/></svg>\n"
+    );
+}
+
+#[test]
+fn test_preset_xhtml() {
+    let xml = "<html xmlns=\"http://www.w3.org/1999/xhtml\"><head><script>init();</script></head>\
+        <body><p class=\"note\" style=\"font-family: Helvetica, Arial, sans-serif\">Hi<br></p></body></html>";
+
+    assert_eq!(
+        run(&["--preset", "xhtml"], xml),
+        "<!-- xmlns=\"http://www.w3.org/1999/xhtml\" -->\n\
+        <html><head><script>…</script></head><body><p class=\"note\" style=\"font-family: Helvetica, Arial, sans-seri…\">Hi<br/></p></body></html>\n"
+    );
+}
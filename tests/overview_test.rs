@@ -0,0 +1,63 @@
+use xml_overview::converter::{self, MinimizeStrategy};
+use xml_overview::{Options, OptionsBuilder, generate_overview};
+
+#[test]
+fn test_generate_overview() {
+    let xml =
+        "<catalog><book id=\"1\"><title>A</title></book><book id=\"2\" lang=\"en\"/></catalog>";
+
+    assert_eq!(
+        generate_overview(xml, Options::default()).unwrap(),
+        "<catalog><book id=\"1\"><title>A</title></book><book id=\"2\" lang=\"en\"/></catalog>"
+    );
+}
+
+#[test]
+fn test_generate_overview_with_builder() {
+    let xml = "<catalog><book id=\"1\"><title>A</title></book><book lang=\"en\"/></catalog>";
+    let options = Options::builder()
+        .strategy(MinimizeStrategy::Merge)
+        .strip_values(true)
+        .build();
+
+    assert_eq!(options.minimize_strategy, MinimizeStrategy::Merge);
+    assert_eq!(
+        generate_overview(xml, options).unwrap(),
+        "<catalog><book id?=\"\" lang?=\"\"><title>A</title></book></catalog>"
+    );
+
+    let options = OptionsBuilder::from(Options::xhtml()).depth(2).build();
+    assert_eq!(options.depth, Some(2));
+    assert_eq!(options.void_elements, Options::xhtml().void_elements);
+}
+
+#[test]
+fn test_generate_overview_error() {
+    assert!(generate_overview("<catalog", Options::default()).is_err());
+}
+
+#[test]
+fn test_generate_overview_deep_document() {
+    let depth = 50_000;
+    let open: String = (0..depth).map(|i| format!("<e{}>", i)).collect();
+    let close: String = (0..depth).rev().map(|i| format!("</e{}>", i)).collect();
+    let xml = format!("{}{}", open, close);
+
+    let overview = generate_overview(&xml, Options::default()).unwrap();
+
+    // Nothing repeats and the innermost element has an explicit closing tag, so the overview is the document.
+    assert_eq!(overview, xml);
+}
+
+#[test]
+fn test_leaf_paths_deep_document() {
+    let depth = 50_000;
+    let open: String = (0..depth).map(|i| format!("<e{}>", i)).collect();
+    let close: String = (0..depth).rev().map(|i| format!("</e{}>", i)).collect();
+    let xml = format!("{}{}", open, close);
+
+    let (paths, _) = converter::convert_leaf_paths(&[xml.as_str()], &Options::default()).unwrap();
+
+    let path: Vec<String> = (0..depth).map(|i| format!("e{}", i)).collect();
+    assert_eq!(paths, path.join("/"));
+}
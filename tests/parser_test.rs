@@ -1,4 +1,4 @@
 #[test]
 fn test_lex_next_token() {
     assert_eq!(1, 1)
-}
\ No newline at end of file
+}